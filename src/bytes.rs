@@ -0,0 +1,173 @@
+use crate::UnsafeCellSlice;
+
+/// The byte order of a value in an [`UnsafeCellSlice<u8>`](UnsafeCellSlice).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Endian {
+    /// Little endian.
+    Little,
+    /// Big endian.
+    Big,
+    /// The native endianness of the target platform.
+    Native,
+}
+
+mod private_primitive {
+    pub trait Sealed {}
+}
+
+/// A primitive numeric type that can be written to and read from bytes with a given [`Endian`].
+///
+/// This is implemented for all primitive integer and floating point types.
+pub trait Primitive: Copy + private_primitive::Sealed {
+    /// The byte representation of the type.
+    type Bytes: AsRef<[u8]> + AsMut<[u8]> + Default;
+
+    /// Convert `self` to bytes with byte order `endian`.
+    fn to_bytes(self, endian: Endian) -> Self::Bytes;
+
+    /// Convert bytes with byte order `endian` to `Self`.
+    fn from_bytes(bytes: Self::Bytes, endian: Endian) -> Self;
+}
+
+macro_rules! impl_primitive {
+    ($($t:ty),*) => {
+        $(
+            impl private_primitive::Sealed for $t {}
+
+            impl Primitive for $t {
+                type Bytes = [u8; core::mem::size_of::<$t>()];
+
+                fn to_bytes(self, endian: Endian) -> Self::Bytes {
+                    match endian {
+                        Endian::Little => self.to_le_bytes(),
+                        Endian::Big => self.to_be_bytes(),
+                        Endian::Native => self.to_ne_bytes(),
+                    }
+                }
+
+                fn from_bytes(bytes: Self::Bytes, endian: Endian) -> Self {
+                    match endian {
+                        Endian::Little => Self::from_le_bytes(bytes),
+                        Endian::Big => Self::from_be_bytes(bytes),
+                        Endian::Native => Self::from_ne_bytes(bytes),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_primitive!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+/// Returns the byte range of a `len` byte value at `offset`, panicking if the range overflows.
+fn byte_range(offset: usize, len: usize) -> core::ops::Range<usize> {
+    let end = offset
+        .checked_add(len)
+        .expect("byte offset overflowed usize");
+    offset..end
+}
+
+impl UnsafeCellSlice<'_, u8> {
+    /// Write a primitive `value` with byte order `endian` at `byte_offset` in the underlying slice.
+    ///
+    /// The value does not need to be aligned.
+    ///
+    /// ```rust
+    /// # use unsafe_cell_slice::{Endian, UnsafeCellSlice};
+    /// let mut data = vec![0u8; 6];
+    /// {
+    ///     let data = UnsafeCellSlice::new(&mut data);
+    ///     unsafe { data.write_at(0, 0x0102u16, Endian::Big) };
+    ///     unsafe { data.write_at(2, 0x03040506u32, Endian::Little) };
+    /// }
+    /// assert_eq!(data, [1, 2, 6, 5, 4, 3]);
+    /// ```
+    ///
+    /// # Safety
+    /// This is very unsafe because it can write to bytes that are referenced elsewhere.
+    /// It is the responsibility of the caller to only access non-overlapping subslices to avoid data races and undefined behavior.
+    ///
+    /// # Panics
+    /// Panics if the value is out of bounds.
+    pub unsafe fn write_at<T: Primitive>(&self, byte_offset: usize, value: T, endian: Endian) {
+        self.write_bytes_at(byte_offset, value.to_bytes(endian).as_ref());
+    }
+
+    /// Read a primitive value with byte order `endian` at `byte_offset` in the underlying slice.
+    ///
+    /// The value does not need to be aligned.
+    ///
+    /// # Safety
+    /// It is the responsibility of the caller to ensure that the bytes are not concurrently written to avoid data races and undefined behavior.
+    ///
+    /// # Panics
+    /// Panics if the value is out of bounds.
+    #[must_use]
+    pub unsafe fn read_at<T: Primitive>(&self, byte_offset: usize, endian: Endian) -> T {
+        let mut bytes = T::Bytes::default();
+        let dst = bytes.as_mut();
+        let range = byte_range(byte_offset, dst.len());
+        let src = self.subslice(range.start, range.end);
+        #[cfg(loom)]
+        crate::loom::track_ref(src.as_mut_ptr(), src.len());
+        core::ptr::copy_nonoverlapping(src.as_mut_ptr(), dst.as_mut_ptr(), dst.len());
+        T::from_bytes(bytes, endian)
+    }
+
+    /// Write `bytes` at `byte_offset` in the underlying slice.
+    ///
+    /// # Safety
+    /// This is very unsafe because it can write to bytes that are referenced elsewhere.
+    /// It is the responsibility of the caller to only access non-overlapping subslices to avoid data races and undefined behavior.
+    ///
+    /// # Panics
+    /// Panics if the bytes are out of bounds.
+    pub unsafe fn write_bytes_at(&self, byte_offset: usize, bytes: &[u8]) {
+        self.index_mut(byte_range(byte_offset, bytes.len()))
+            .copy_from_slice(bytes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_read_at() {
+        let mut data = vec![0u8; 32];
+        {
+            let data = UnsafeCellSlice::new(&mut data);
+            unsafe {
+                data.write_at(1, -2i16, Endian::Little);
+                data.write_at(3, 1.5f64, Endian::Big);
+                data.write_at(11, u128::MAX - 1, Endian::Native);
+                data.write_bytes_at(27, b"abcde");
+                assert_eq!(data.read_at::<i16>(1, Endian::Little), -2);
+                assert_eq!(data.read_at::<u16>(1, Endian::Big), 0xfeff);
+                assert_eq!(data.read_at::<f64>(3, Endian::Big), 1.5);
+                assert_eq!(data.read_at::<u128>(11, Endian::Native), u128::MAX - 1);
+                assert_eq!(data.read_at::<u8>(31, Endian::Native), b'e');
+            }
+        }
+        assert_eq!(data[0], 0);
+        assert_eq!(data[1..3], [0xfe, 0xff]);
+        assert_eq!(data[3..11], 1.5f64.to_be_bytes());
+        assert_eq!(&data[27..], b"abcde");
+    }
+
+    #[test]
+    #[should_panic]
+    fn write_at_out_of_bounds() {
+        let mut data = vec![0u8; 4];
+        let data = UnsafeCellSlice::new(&mut data);
+        unsafe { data.write_at(1, 0u32, Endian::Little) };
+    }
+
+    #[test]
+    #[should_panic]
+    fn read_at_overflow() {
+        let mut data = vec![0u8; 4];
+        let data = UnsafeCellSlice::new(&mut data);
+        let _ = unsafe { data.read_at::<u32>(usize::MAX, Endian::Little) };
+    }
+}
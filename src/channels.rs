@@ -0,0 +1,259 @@
+use crate::UnsafeCellSlice;
+
+impl<'a, T> UnsafeCellSlice<'a, T> {
+    /// Split an interleaved slice of `K`-element frames (e.g. audio samples or RGB(A) pixels) into `K` channels.
+    ///
+    /// Channel `c` contains elements `c, c + K, c + 2K, ...` of the underlying slice, so the channels are disjoint and can be processed on separate threads.
+    ///
+    /// ```rust
+    /// # use unsafe_cell_slice::UnsafeCellSlice;
+    /// let mut rgb = vec![0u8; 3 * 4];
+    /// {
+    ///     let rgb = UnsafeCellSlice::new(&mut rgb);
+    ///     let channels = unsafe { rgb.channels::<3>() };
+    ///     std::thread::scope(|s| {
+    ///         for (c, mut channel) in channels.into_iter().enumerate() {
+    ///             s.spawn(move || channel.iter_mut().for_each(|v| *v = c as u8));
+    ///         }
+    ///     });
+    /// }
+    /// assert_eq!(&rgb[..6], [0, 1, 2, 0, 1, 2]);
+    /// ```
+    ///
+    /// # Safety
+    /// The channels permit safe mutable access to all elements of the underlying slice.
+    /// It is the responsibility of the caller to not access the elements of the underlying slice through any other reference while the channels exist.
+    ///
+    /// # Panics
+    /// Panics if `K` is zero or the length of the underlying slice is not a multiple of `K`.
+    #[must_use]
+    pub unsafe fn channels<const K: usize>(&self) -> [Channel<'a, T>; K] {
+        assert!(K != 0, "number of channels must be non-zero");
+        assert!(
+            self.len() % K == 0,
+            "slice length must be a multiple of the number of channels"
+        );
+        let data = *self;
+        core::array::from_fn(|channel| Channel {
+            data,
+            channel,
+            stride: K,
+        })
+    }
+}
+
+/// A channel of an interleaved slice with safe mutable access to its elements.
+///
+/// Element `i` of channel `c` of a `K`-channel slice is element `i * K + c` of the slice.
+/// Created by [`UnsafeCellSlice::channels`] or [`Channel::split`].
+///
+/// A [`Channel`] is [`Send`] if `T` is [`Send`].
+/// It is only [`Sync`] if `T` is [`Sync`], since [`get`](Channel::get) and [`iter`](Channel::iter) return shared references:
+/// ```rust,compile_fail
+/// # use std::cell::Cell;
+/// # use unsafe_cell_slice::Channel;
+/// fn assert_sync<T: Sync>(_: &T) {}
+/// let mut data = vec![Cell::new(0u8); 2];
+/// let [left, _] = Channel::split::<2>(&mut data);
+/// assert_sync(&left);
+/// ```
+pub struct Channel<'a, T> {
+    data: UnsafeCellSlice<'a, T>,
+    channel: usize,
+    stride: usize,
+}
+
+unsafe impl<T: Send> Send for Channel<'_, T> {}
+unsafe impl<T: Sync> Sync for Channel<'_, T> {}
+
+impl<'a, T> Channel<'a, T> {
+    /// Split a mutable interleaved slice of `K`-element frames into `K` channels.
+    ///
+    /// # Panics
+    /// Panics if `K` is zero or the length of `slice` is not a multiple of `K`.
+    #[must_use]
+    pub fn split<const K: usize>(slice: &'a mut [T]) -> [Self; K] {
+        // SAFETY: the slice is exclusively borrowed for the lifetime of the channels
+        unsafe { UnsafeCellSlice::new(slice).channels::<K>() }
+    }
+
+    /// Return the index of this channel.
+    #[must_use]
+    pub fn channel(&self) -> usize {
+        self.channel
+    }
+
+    /// Return the number of elements in the channel.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.data.len() / self.stride
+    }
+
+    /// Return whether the channel is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get a reference to element `index` of the channel, or `None` if out of bounds.
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index < self.len() {
+            // SAFETY: the channel has exclusive access to its elements
+            Some(unsafe {
+                let ptr = self
+                    .data
+                    .as_mut_ptr()
+                    .add(index * self.stride + self.channel);
+                #[cfg(loom)]
+                crate::loom::track_ref(ptr, 1);
+                &*ptr
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Get a mutable reference to element `index` of the channel, or `None` if out of bounds.
+    #[must_use]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index < self.len() {
+            // SAFETY: the channel has exclusive access to its elements and is mutably borrowed
+            unsafe { self.data.get_mut(index * self.stride + self.channel) }
+        } else {
+            None
+        }
+    }
+
+    /// Return an iterator over the elements of the channel.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &T> + '_ {
+        let (data, channel, stride) = (self.data.as_mut_ptr(), self.channel, self.stride);
+        // SAFETY: the channel has exclusive access to its elements
+        (0..self.len()).map(move |index| unsafe {
+            let ptr = data.add(index * stride + channel);
+            #[cfg(loom)]
+            crate::loom::track_ref(ptr, 1);
+            &*ptr
+        })
+    }
+
+    /// Return an iterator over mutable references to the elements of the channel.
+    pub fn iter_mut(&mut self) -> impl ExactSizeIterator<Item = &mut T> + '_ {
+        let (data, channel, stride) = (self.data.as_mut_ptr(), self.channel, self.stride);
+        // SAFETY: the channel has exclusive access to its elements and is mutably borrowed, and each element is visited once
+        (0..self.len()).map(move |index| unsafe {
+            let ptr = data.add(index * stride + channel);
+            #[cfg(loom)]
+            crate::loom::track_mut(ptr, 1);
+            &mut *ptr
+        })
+    }
+
+    /// Copy the elements of the channel into a planar slice.
+    ///
+    /// # Panics
+    /// Panics if the length of `planar` differs from the length of the channel.
+    pub fn deinterleave_into(&self, planar: &mut [T])
+    where
+        T: Copy,
+    {
+        assert_eq!(
+            planar.len(),
+            self.len(),
+            "planar slice length must match the channel length"
+        );
+        for (dst, src) in planar.iter_mut().zip(self.iter()) {
+            *dst = *src;
+        }
+    }
+
+    /// Copy the elements of a planar slice into the channel.
+    ///
+    /// # Panics
+    /// Panics if the length of `planar` differs from the length of the channel.
+    pub fn interleave_from(&mut self, planar: &[T])
+    where
+        T: Copy,
+    {
+        assert_eq!(
+            planar.len(),
+            self.len(),
+            "planar slice length must match the channel length"
+        );
+        for (dst, src) in self.iter_mut().zip(planar) {
+            *dst = *src;
+        }
+    }
+}
+
+impl<T> core::ops::Index<usize> for Channel<'_, T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        self.get(index).expect("index out of bounds")
+    }
+}
+
+impl<T> core::ops::IndexMut<usize> for Channel<'_, T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        self.get_mut(index).expect("index out of bounds")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::vec::Vec;
+
+    #[test]
+    fn channels() {
+        let mut data: Vec<u32> = (0..12).collect();
+        let [mut a, b, c, d] = Channel::split::<4>(&mut data);
+        assert_eq!((a.channel(), d.channel()), (0, 3));
+        assert_eq!(a.len(), 3);
+        assert_eq!(b.iter().copied().collect::<Vec<_>>(), [1, 5, 9]);
+        assert_eq!(c.get(2), Some(&10));
+        assert!(c.get(3).is_none());
+        a[1] = 40;
+        assert_eq!(a[1], 40);
+        assert!(a.get_mut(3).is_none());
+        a.iter_mut().for_each(|v| *v += 1);
+        assert_eq!(&data[..5], [1, 1, 2, 3, 41]);
+    }
+
+    #[test]
+    fn channels_interleave() {
+        let mut interleaved = vec![0i16; 6];
+        let left = [1, 2, 3];
+        let right = [4, 5, 6];
+        {
+            let [mut l, mut r] = Channel::split::<2>(&mut interleaved);
+            l.interleave_from(&left);
+            r.interleave_from(&right);
+        }
+        assert_eq!(interleaved, [1, 4, 2, 5, 3, 6]);
+        let mut planar = [0; 3];
+        let [_, r] = Channel::split::<2>(&mut interleaved);
+        r.deinterleave_into(&mut planar);
+        assert_eq!(planar, right);
+    }
+
+    #[test]
+    #[should_panic]
+    fn channels_partial_frame() {
+        let mut data = vec![0u8; 5];
+        let _ = Channel::split::<2>(&mut data);
+    }
+
+    #[test]
+    fn channels_par() {
+        use rayon::prelude::*;
+        let mut data = vec![0usize; 3 * 100];
+        let channels = Channel::split::<3>(&mut data);
+        channels.into_par_iter().for_each(|mut channel| {
+            let c = channel.channel();
+            channel.iter_mut().for_each(|v| *v = c);
+        });
+        assert!(data.iter().enumerate().all(|(i, v)| *v == i % 3));
+    }
+}
@@ -0,0 +1,130 @@
+use crate::UnsafeCellSlice;
+
+impl<'a, T> UnsafeCellSlice<'a, T> {
+    /// Split the underlying slice into `N`-element array chunks, starting at the beginning of the slice, and a remainder with length strictly less than `N`.
+    ///
+    /// This is the cell slice equivalent of [`slice::as_chunks_mut`](https://doc.rust-lang.org/std/primitive.slice.html#method.as_chunks_mut).
+    ///
+    /// # Panics
+    /// Panics if `N` is zero.
+    #[must_use]
+    pub fn as_chunks<const N: usize>(
+        &self,
+    ) -> (UnsafeCellSlice<'a, [T; N]>, UnsafeCellSlice<'a, T>) {
+        assert!(N != 0, "chunk size must be non-zero");
+        let mid = self.len() / N * N;
+        // SAFETY: the first `mid` elements are `mid / N` arrays of `N` elements with the alignment of `T`
+        let chunks = unsafe { self.subslice(0, mid).cast_unchecked::<[T; N]>(mid / N) };
+        (chunks, self.subslice(mid, self.len()))
+    }
+
+    /// Split the underlying slice into an unaligned head, a middle of `N`-element array chunks, and a tail.
+    ///
+    /// The first chunk of the middle starts at an address that is a multiple of `align` bytes.
+    /// As with [`slice::align_to`], the head may be the entire slice if the alignment cannot be reached.
+    ///
+    /// # Panics
+    /// Panics if `N` is zero or `align` is not a power of two.
+    #[must_use]
+    #[allow(clippy::type_complexity)]
+    pub fn as_aligned_chunks<const N: usize>(
+        &self,
+        align: usize,
+    ) -> (
+        UnsafeCellSlice<'a, T>,
+        UnsafeCellSlice<'a, [T; N]>,
+        UnsafeCellSlice<'a, T>,
+    ) {
+        assert!(N != 0, "chunk size must be non-zero");
+        assert!(align.is_power_of_two(), "align must be a power of two");
+        let offset = self.as_mut_ptr().align_offset(align).min(self.len());
+        let (middle, tail) = self.subslice(offset, self.len()).as_chunks::<N>();
+        (self.subslice(0, offset), middle, tail)
+    }
+
+    /// Get a mutable reference to an `N`-element array starting at `offset` in the underlying slice.
+    ///
+    /// Returns `None` if the array is out of bounds.
+    ///
+    /// # Safety
+    /// This is very unsafe because it is capable of creating multiple mutable references to the same data.
+    /// It is the responsibility of the caller to only access non-overlapping subslices to avoid data races and undefined behavior.
+    #[must_use]
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn get_array_mut<const N: usize>(&self, offset: usize) -> Option<&mut [T; N]> {
+        let end = offset.checked_add(N)?;
+        self.get_mut(offset..end)
+            .map(|s| s.try_into().expect("subslice has length N"))
+    }
+}
+
+impl<'a, T, const N: usize> UnsafeCellSlice<'a, [T; N]> {
+    /// Flatten an [`UnsafeCellSlice`] of `N`-element arrays into an [`UnsafeCellSlice`] of elements.
+    ///
+    /// This is the inverse of [`UnsafeCellSlice::as_chunks`].
+    ///
+    /// # Panics
+    /// Panics if the length of the flattened slice would overflow a [`usize`].
+    #[must_use]
+    pub fn flatten(&self) -> UnsafeCellSlice<'a, T> {
+        let len = self.len().checked_mul(N).expect("slice len overflow");
+        // SAFETY: an array of `N` elements is `N` contiguous elements
+        unsafe { self.cast_unchecked::<T>(len) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn as_chunks() {
+        let mut data = vec![0u16, 1, 2, 3, 4, 5, 6];
+        let data = UnsafeCellSlice::new(&mut data);
+        let (chunks, remainder) = data.as_chunks::<3>();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(remainder.len(), 1);
+        assert_eq!(unsafe { chunks.index_mut(1) }, &[3, 4, 5]);
+        assert_eq!(unsafe { remainder.index_mut(0) }, &6);
+        let flat = chunks.flatten();
+        assert_eq!(flat.len(), 6);
+        assert_eq!(unsafe { flat.index_mut(..) }, [0, 1, 2, 3, 4, 5]);
+
+        let (chunks, remainder) = data.as_chunks::<8>();
+        assert!(chunks.is_empty());
+        assert_eq!(remainder.len(), 7);
+    }
+
+    #[test]
+    #[should_panic]
+    fn as_chunks_zero() {
+        let mut data = vec![0u8; 4];
+        let data = UnsafeCellSlice::new(&mut data);
+        let _ = data.as_chunks::<0>();
+    }
+
+    #[test]
+    fn get_array_mut() {
+        let mut data = vec![0u8, 1, 2, 3, 4];
+        let data = UnsafeCellSlice::new(&mut data);
+        let array: &mut [u8; 2] = unsafe { data.get_array_mut(1) }.unwrap();
+        assert_eq!(array, &[1, 2]);
+        array[0] = 10;
+        assert_eq!(unsafe { data.index_mut(1) }, &10);
+        assert!(unsafe { data.get_array_mut::<2>(3) }.is_some());
+        assert!(unsafe { data.get_array_mut::<2>(4) }.is_none());
+        assert!(unsafe { data.get_array_mut::<2>(usize::MAX) }.is_none());
+    }
+
+    #[test]
+    fn as_aligned_chunks() {
+        let mut data = vec![0u32; 64];
+        let data = UnsafeCellSlice::new(&mut data);
+        let data = unsafe { data.index_mut(1..) };
+        let data = UnsafeCellSlice::new(data);
+        let (head, middle, tail) = data.as_aligned_chunks::<4>(16);
+        assert_eq!(head.len() + middle.len() * 4 + tail.len(), 63);
+        assert!(tail.len() < 4);
+        assert_eq!(unsafe { middle.index_mut(0) }.as_ptr() as usize % 16, 0);
+    }
+}
@@ -0,0 +1,220 @@
+use crate::UnsafeCellSlice;
+
+mod private_const_index {
+    pub trait Sealed {}
+}
+
+/// A compile-time element index for [`UnsafeCellSlice::get_const_mut`].
+#[derive(Copy, Clone, Debug)]
+pub struct ConstIndex<const INDEX: usize>;
+
+/// A compile-time subslice range `START..END` for [`UnsafeCellSlice::get_const_mut`].
+#[derive(Copy, Clone, Debug)]
+pub struct ConstRange<const START: usize, const END: usize>;
+
+/// A compile-time index into an [`UnsafeCellSlice`].
+///
+/// This is implemented for [`ConstIndex`] and [`ConstRange`].
+///
+/// # Safety
+/// Callers of the trait methods must ensure that overlapping subslices/elements are not requested.
+pub unsafe trait ConstSliceIndex<'a, T: 'a>: private_const_index::Sealed {
+    /// The start of the indexed elements.
+    const START: usize;
+
+    /// The end (exclusive) of the indexed elements.
+    const END: usize;
+
+    /// The output reference type.
+    type Output;
+
+    /// Returns a mutable reference to the output at this location, panicking
+    /// if out of bounds.
+    ///
+    /// # Safety
+    /// The caller must ensure that overlapping subslices/elements are not requested.
+    unsafe fn index_mut(slice: &'a UnsafeCellSlice<'_, T>) -> Self::Output;
+}
+
+/// A set of compile-time indices into an [`UnsafeCellSlice`] that are disjoint.
+///
+/// This is implemented for [`ConstIndex`], [`ConstRange`], and tuples of up to 8 of those.
+/// Disjointness of the indices in a tuple is asserted at compile time.
+///
+/// # Safety
+/// Callers of the trait methods must ensure that overlapping subslices/elements are not requested.
+pub unsafe trait ConstSliceIndices<'a, T: 'a>: private_const_index::Sealed {
+    /// The minimum length of a slice for the indices to be in bounds.
+    const END: usize;
+
+    /// Evaluating this constant fails compilation if the indices overlap.
+    const DISJOINT: ();
+
+    /// The output reference type(s).
+    type Output;
+
+    /// Returns mutable references to the outputs at these locations, panicking
+    /// if out of bounds.
+    ///
+    /// # Safety
+    /// The caller must ensure that overlapping subslices/elements are not requested.
+    unsafe fn index_mut(slice: &'a UnsafeCellSlice<'_, T>) -> Self::Output;
+}
+
+impl<const INDEX: usize> private_const_index::Sealed for ConstIndex<INDEX> {}
+impl<const START: usize, const END: usize> private_const_index::Sealed for ConstRange<START, END> {}
+
+unsafe impl<'a, T: 'a, const INDEX: usize> ConstSliceIndex<'a, T> for ConstIndex<INDEX> {
+    const START: usize = INDEX;
+    const END: usize = INDEX + 1;
+    type Output = &'a mut T;
+
+    unsafe fn index_mut(slice: &'a UnsafeCellSlice<'_, T>) -> Self::Output {
+        slice.index_mut(INDEX)
+    }
+}
+
+unsafe impl<'a, T: 'a, const START: usize, const END: usize> ConstSliceIndex<'a, T>
+    for ConstRange<START, END>
+{
+    const START: usize = START;
+    const END: usize = {
+        assert!(START <= END, "const range starts after it ends");
+        END
+    };
+    type Output = &'a mut [T];
+
+    unsafe fn index_mut(slice: &'a UnsafeCellSlice<'_, T>) -> Self::Output {
+        slice.index_mut(START..END)
+    }
+}
+
+unsafe impl<'a, T: 'a, const INDEX: usize> ConstSliceIndices<'a, T> for ConstIndex<INDEX> {
+    const END: usize = <Self as ConstSliceIndex<'a, T>>::END;
+    const DISJOINT: () = ();
+    type Output = <Self as ConstSliceIndex<'a, T>>::Output;
+
+    unsafe fn index_mut(slice: &'a UnsafeCellSlice<'_, T>) -> Self::Output {
+        <Self as ConstSliceIndex<'a, T>>::index_mut(slice)
+    }
+}
+
+unsafe impl<'a, T: 'a, const START: usize, const END: usize> ConstSliceIndices<'a, T>
+    for ConstRange<START, END>
+{
+    const END: usize = <Self as ConstSliceIndex<'a, T>>::END;
+    const DISJOINT: () = ();
+    type Output = <Self as ConstSliceIndex<'a, T>>::Output;
+
+    unsafe fn index_mut(slice: &'a UnsafeCellSlice<'_, T>) -> Self::Output {
+        <Self as ConstSliceIndex<'a, T>>::index_mut(slice)
+    }
+}
+
+/// Panics (at compile time if evaluated in a const context) if any of the non-empty `ranges` overlap.
+const fn assert_disjoint(ranges: &[(usize, usize)]) {
+    let mut i = 0;
+    while i < ranges.len() {
+        let mut j = i + 1;
+        while j < ranges.len() {
+            let (a, b) = (ranges[i], ranges[j]);
+            let empty = a.0 == a.1 || b.0 == b.1;
+            assert!(
+                empty || a.0 >= b.1 || b.0 >= a.1,
+                "const indices are not disjoint"
+            );
+            j += 1;
+        }
+        i += 1;
+    }
+}
+
+const fn max(a: usize, b: usize) -> usize {
+    if a > b {
+        a
+    } else {
+        b
+    }
+}
+
+macro_rules! impl_const_slice_indices_tuple {
+    ($($name:ident),+) => {
+        impl<$($name),+> private_const_index::Sealed for ($($name,)+) {}
+
+        unsafe impl<'a, T: 'a, $($name: ConstSliceIndex<'a, T>),+> ConstSliceIndices<'a, T>
+            for ($($name,)+)
+        {
+            const END: usize = {
+                let end = 0;
+                $(let end = max(end, $name::END);)+
+                end
+            };
+            const DISJOINT: () = assert_disjoint(&[$(($name::START, $name::END)),+]);
+            type Output = ($($name::Output,)+);
+
+            unsafe fn index_mut(slice: &'a UnsafeCellSlice<'_, T>) -> Self::Output {
+                ($($name::index_mut(slice),)+)
+            }
+        }
+    };
+}
+
+impl_const_slice_indices_tuple!(A);
+impl_const_slice_indices_tuple!(A, B);
+impl_const_slice_indices_tuple!(A, B, C);
+impl_const_slice_indices_tuple!(A, B, C, D);
+impl_const_slice_indices_tuple!(A, B, C, D, E);
+impl_const_slice_indices_tuple!(A, B, C, D, E, F);
+impl_const_slice_indices_tuple!(A, B, C, D, E, F, G);
+impl_const_slice_indices_tuple!(A, B, C, D, E, F, G, H);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn const_index() {
+        let mut data = vec![0u8, 1, 2, 3];
+        let data = UnsafeCellSlice::new(&mut data);
+        let (r, g, b, a) = unsafe {
+            data.get_const_mut::<(ConstIndex<0>, ConstIndex<1>, ConstIndex<2>, ConstIndex<3>)>()
+        }
+        .unwrap();
+        assert_eq!((*r, *g, *b, *a), (0, 1, 2, 3));
+        *r = 4;
+        *a = 7;
+        assert_eq!(*unsafe { data.index_mut(0) }, 4);
+        assert_eq!(*unsafe { data.index_mut(3) }, 7);
+        assert_eq!(
+            *unsafe { data.get_const_mut::<ConstIndex<1>>() }.unwrap(),
+            1
+        );
+        assert!(unsafe { data.get_const_mut::<ConstIndex<4>>() }.is_none());
+        assert!(unsafe { data.get_const_mut::<(ConstIndex<0>, ConstIndex<4>)>() }.is_none());
+    }
+
+    #[test]
+    fn const_range() {
+        let mut data = vec![0i64, 1, 2, 3, 4];
+        let data = UnsafeCellSlice::new(&mut data);
+        let (head, mid, tail) = unsafe {
+            data.index_const_mut::<(ConstRange<0, 2>, ConstIndex<2>, ConstRange<3, 5>)>()
+        };
+        assert_eq!(head, [0, 1]);
+        assert_eq!(*mid, 2);
+        assert_eq!(tail, [3, 4]);
+        let (empty, all) =
+            unsafe { data.index_const_mut::<(ConstRange<2, 2>, ConstRange<0, 5>)>() };
+        assert!(empty.is_empty());
+        assert_eq!(all.len(), 5);
+        assert!(unsafe { data.get_const_mut::<ConstRange<3, 6>>() }.is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn const_index_out_of_bounds() {
+        let mut data = vec![0u8; 2];
+        let data = UnsafeCellSlice::new(&mut data);
+        let _ = unsafe { data.index_const_mut::<(ConstIndex<0>, ConstIndex<2>)>() };
+    }
+}
@@ -0,0 +1,261 @@
+use std::{
+    fs::File,
+    ops::Range,
+    os::unix::fs::FileExt,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::{slice_index::check_range, IndexError, UnsafeCellSlice};
+
+/// An error reading or writing ranges of a file with [`read_ranges_into`] or [`write_ranges_from`].
+#[derive(Debug)]
+pub enum FileRangesError {
+    /// The slice range at position `range` is out of bounds of the slice.
+    OutOfBounds {
+        /// The position of the range.
+        range: usize,
+        /// The index error.
+        error: IndexError,
+    },
+    /// The destination slice ranges at positions `first` and `second` overlap.
+    Overlapping {
+        /// The position of the first range.
+        first: usize,
+        /// The position of the second range.
+        second: usize,
+    },
+    /// Reading or writing some ranges failed.
+    ///
+    /// Each error is paired with the position of its range, in ascending order.
+    Io(Vec<(usize, std::io::Error)>),
+}
+
+impl core::fmt::Display for FileRangesError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::OutOfBounds { range, error } => write!(f, "range {range} is invalid: {error}"),
+            Self::Overlapping { first, second } => {
+                write!(f, "ranges {first} and {second} overlap")
+            }
+            Self::Io(errors) => {
+                let plural = if errors.len() == 1 { "" } else { "s" };
+                write!(f, "{} range{plural} failed", errors.len())?;
+                for (range, error) in errors {
+                    write!(f, "; range {range}: {error}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for FileRangesError {}
+
+/// Read ranges of `file` into disjoint regions of `dst` in parallel.
+///
+/// Each entry of `ranges` is a file offset and a range of `dst`, which is filled with the bytes of the file starting at that offset.
+/// The ranges are read with positional reads ([`FileExt::read_exact_at`]) on multiple threads, so the file position is not changed.
+///
+/// ```rust
+/// # use std::io::Write;
+/// # use unsafe_cell_slice::{read_ranges_into, UnsafeCellSlice};
+/// # let path = std::env::temp_dir().join("unsafe_cell_slice_read_ranges_doctest.bin");
+/// # std::fs::File::create(&path)?.write_all(b"abcdef")?;
+/// let file = std::fs::File::open(&path)?;
+/// let mut data = vec![0u8; 4];
+/// unsafe { read_ranges_into(&file, &[(4, 0..2), (0, 2..4)], UnsafeCellSlice::new(&mut data)) }?;
+/// assert_eq!(data, b"efab");
+/// # std::fs::remove_file(&path)?;
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+/// Returns a [`FileRangesError`] if a range of `dst` is out of bounds or overlaps another range, in which case nothing is read.
+/// Otherwise, all ranges are read and the errors of any failed reads are returned together.
+///
+/// # Safety
+/// It is the responsibility of the caller to not access the ranges of `dst` through any other reference while they are read.
+pub unsafe fn read_ranges_into(
+    file: &File,
+    ranges: &[(u64, Range<usize>)],
+    dst: UnsafeCellSlice<u8>,
+) -> Result<(), FileRangesError> {
+    check_ranges(ranges, dst.len())?;
+    check_disjoint(ranges)?;
+    for_each_range(ranges, |offset, range| {
+        // SAFETY: the ranges are in bounds and disjoint
+        file.read_exact_at(unsafe { dst.index_mut(range) }, offset)
+    })
+}
+
+/// Write regions of `src` to ranges of `file` in parallel.
+///
+/// Each entry of `ranges` is a file offset and a range of `src`, which is written to the file starting at that offset.
+/// The ranges are written with positional writes ([`FileExt::write_all_at`]) on multiple threads, so the file position is not changed.
+/// The regions of `src` may overlap, but the contents of overlapping ranges of the file are unspecified.
+///
+/// # Errors
+/// Returns a [`FileRangesError`] if a range of `src` is out of bounds, in which case nothing is written.
+/// Otherwise, all ranges are written and the errors of any failed writes are returned together.
+///
+/// # Safety
+/// It is the responsibility of the caller to not write to the ranges of `src` through any other reference while they are written to the file.
+pub unsafe fn write_ranges_from(
+    file: &File,
+    ranges: &[(u64, Range<usize>)],
+    src: UnsafeCellSlice<u8>,
+) -> Result<(), FileRangesError> {
+    check_ranges(ranges, src.len())?;
+    for_each_range(ranges, |offset, range| {
+        let src = src.subslice(range.start, range.end);
+        #[cfg(loom)]
+        crate::loom::track_ref(src.as_mut_ptr(), src.len());
+        // SAFETY: the range is in bounds and the caller guarantees it is not written concurrently
+        let src = unsafe { core::slice::from_raw_parts(src.as_mut_ptr(), src.len()) };
+        file.write_all_at(src, offset)
+    })
+}
+
+/// Check that the slice ranges are in bounds of a slice of length `len`.
+fn check_ranges(ranges: &[(u64, Range<usize>)], len: usize) -> Result<(), FileRangesError> {
+    for (position, (_, range)) in ranges.iter().enumerate() {
+        check_range(range.start, range.end, len).map_err(|error| FileRangesError::OutOfBounds {
+            range: position,
+            error,
+        })?;
+    }
+    Ok(())
+}
+
+/// Check that the non-empty slice ranges do not overlap.
+fn check_disjoint(ranges: &[(u64, Range<usize>)]) -> Result<(), FileRangesError> {
+    let mut order: Vec<usize> = (0..ranges.len())
+        .filter(|&position| !ranges[position].1.is_empty())
+        .collect();
+    order.sort_unstable_by_key(|&position| ranges[position].1.start);
+    for pair in order.windows(2) {
+        let (first, second) = (pair[0].min(pair[1]), pair[0].max(pair[1]));
+        if ranges[pair[0]].1.end > ranges[pair[1]].1.start {
+            return Err(FileRangesError::Overlapping { first, second });
+        }
+    }
+    Ok(())
+}
+
+/// Call `f` with each file offset and slice range on a pool of scoped threads, and collect the errors.
+fn for_each_range<F>(ranges: &[(u64, Range<usize>)], f: F) -> Result<(), FileRangesError>
+where
+    F: Fn(u64, Range<usize>) -> std::io::Result<()> + Sync,
+{
+    let threads = std::thread::available_parallelism()
+        .map_or(1, usize::from)
+        .min(ranges.len());
+    let next = AtomicUsize::new(0);
+    let mut errors: Vec<(usize, std::io::Error)> = std::thread::scope(|s| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| {
+                s.spawn(|| {
+                    let mut errors = Vec::new();
+                    loop {
+                        let position = next.fetch_add(1, Ordering::Relaxed);
+                        match ranges.get(position) {
+                            Some((offset, range)) => {
+                                if let Err(error) = f(*offset, range.clone()) {
+                                    errors.push((position, error));
+                                }
+                            }
+                            None => break errors,
+                        }
+                    }
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("range worker does not panic"))
+            .collect()
+    });
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        errors.sort_unstable_by_key(|(position, _)| *position);
+        Err(FileRangesError::Io(errors))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_file(name: &str, contents: &[u8]) -> (std::path::PathBuf, File) {
+        let path = std::env::temp_dir().join(format!(
+            "unsafe_cell_slice_{name}_{}.bin",
+            std::process::id()
+        ));
+        std::fs::write(&path, contents).unwrap();
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&path)
+            .unwrap();
+        (path, file)
+    }
+
+    #[test]
+    fn file_read_ranges_into() {
+        let contents: Vec<u8> = (0..=255).collect();
+        let (path, file) = temp_file("read_ranges_into", &contents);
+        let mut data = vec![0u8; 64];
+        let ranges: Vec<(u64, Range<usize>)> = (0..16)
+            .map(|i| (256 - 4 * (i + 1) as u64, 4 * i..4 * (i + 1)))
+            .collect();
+        unsafe { read_ranges_into(&file, &ranges, UnsafeCellSlice::new(&mut data)) }.unwrap();
+        assert_eq!(&data[..8], [252, 253, 254, 255, 248, 249, 250, 251]);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn file_read_ranges_into_errors() {
+        let (path, file) = temp_file("read_ranges_into_errors", b"abcd");
+        let mut data = vec![0u8; 8];
+        let data = UnsafeCellSlice::new(&mut data);
+        assert!(matches!(
+            unsafe { read_ranges_into(&file, &[(0, 4..9)], data) },
+            Err(FileRangesError::OutOfBounds {
+                range: 0,
+                error: IndexError::EndOutOfRange { end: 9, len: 8 }
+            })
+        ));
+        assert!(matches!(
+            unsafe { read_ranges_into(&file, &[(0, 4..6), (0, 2..2), (0, 0..5)], data) },
+            Err(FileRangesError::Overlapping {
+                first: 0,
+                second: 2
+            })
+        ));
+        let errors =
+            match unsafe { read_ranges_into(&file, &[(2, 0..4), (0, 4..6), (4, 6..7)], data) } {
+                Err(FileRangesError::Io(errors)) => errors,
+                _ => panic!("reading past the end of the file fails"),
+            };
+        assert_eq!(
+            errors.iter().map(|(range, _)| *range).collect::<Vec<_>>(),
+            [0, 2]
+        );
+        assert_eq!(errors[0].1.kind(), std::io::ErrorKind::UnexpectedEof);
+        let err = unsafe { read_ranges_into(&file, &[(4, 0..1)], data) }.unwrap_err();
+        assert!(err.to_string().starts_with("1 range failed; range 0: "));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn file_write_ranges_from() {
+        let (path, file) = temp_file("write_ranges_from", b"");
+        let mut data = *b"abcdef";
+        let data = UnsafeCellSlice::new(&mut data);
+        unsafe { write_ranges_from(&file, &[(3, 0..3), (0, 3..6), (6, 0..6)], data) }.unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"defabcabcdef");
+        assert!(unsafe { write_ranges_from(&file, &[(0, 5..7)], data) }.is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,160 @@
+use std::io::{Seek, SeekFrom, Write};
+
+use crate::{SliceIndex, UnsafeCellSlice};
+
+/// An [`io::Write`](std::io::Write) and [`io::Seek`](std::io::Seek) adaptor over a region of an [`UnsafeCellSlice<u8>`](UnsafeCellSlice).
+///
+/// Created by [`UnsafeCellSlice::region_writer`].
+/// Writes past the end of the region fail with [`ErrorKind::WriteZero`](std::io::ErrorKind::WriteZero).
+#[derive(Debug)]
+pub struct RegionWriter<'a> {
+    region: &'a mut [u8],
+    position: usize,
+    bytes_written: usize,
+}
+
+impl<'a> RegionWriter<'a> {
+    /// Create a new [`RegionWriter`] over a mutable byte slice.
+    #[must_use]
+    pub fn new(region: &'a mut [u8]) -> Self {
+        Self {
+            region,
+            position: 0,
+            bytes_written: 0,
+        }
+    }
+
+    /// Return the length of the region.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.region.len()
+    }
+
+    /// Return whether the region is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.region.is_empty()
+    }
+
+    /// Return the current position in the region.
+    #[must_use]
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Return the number of bytes written from the start of the region.
+    ///
+    /// This is the end of the furthest write, which may be beyond the current position after seeking backwards.
+    #[must_use]
+    pub fn bytes_written(&self) -> usize {
+        self.bytes_written
+    }
+}
+
+impl Write for RegionWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let remaining = &mut self.region[self.position..];
+        if remaining.is_empty() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::WriteZero,
+                "region writer is full",
+            ));
+        }
+        let len = buf.len().min(remaining.len());
+        remaining[..len].copy_from_slice(&buf[..len]);
+        self.position += len;
+        self.bytes_written = self.bytes_written.max(self.position);
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Seek for RegionWriter<'_> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let (base, offset) = match pos {
+            SeekFrom::Start(offset) => (0, i128::from(offset)),
+            SeekFrom::End(offset) => (self.len(), i128::from(offset)),
+            SeekFrom::Current(offset) => (self.position, i128::from(offset)),
+        };
+        let position = base as i128 + offset;
+        if position < 0 || position > self.len() as i128 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "seek out of region bounds",
+            ));
+        }
+        self.position = usize::try_from(position).expect("position is in bounds");
+        Ok(self.position as u64)
+    }
+}
+
+impl UnsafeCellSlice<'_, u8> {
+    /// Create a [`RegionWriter`] over a subslice of the underlying slice.
+    ///
+    /// This permits each thread to hand its region of a shared output buffer to an [`io::Write`](std::io::Write) based encoder.
+    ///
+    /// ```rust
+    /// # use std::io::Write;
+    /// # use unsafe_cell_slice::UnsafeCellSlice;
+    /// let mut data = vec![0u8; 8];
+    /// {
+    ///     let data = UnsafeCellSlice::new(&mut data);
+    ///     let mut writer = unsafe { data.region_writer(2..6) };
+    ///     writer.write_all(b"abc").unwrap();
+    ///     assert_eq!(writer.bytes_written(), 3);
+    ///     assert!(writer.write_all(b"de").is_err());
+    /// }
+    /// assert_eq!(&data, b"\0\0abcd\0\0");
+    /// ```
+    ///
+    /// # Safety
+    /// This is very unsafe because it is capable of creating multiple mutable references to the same data.
+    /// It is the responsibility of the caller to only access non-overlapping subslices to avoid data races and undefined behavior.
+    ///
+    /// # Panics
+    /// Panics if the region is out of bounds.
+    #[must_use]
+    pub unsafe fn region_writer<I>(&self, range: I) -> RegionWriter<'_>
+    where
+        I: SliceIndex<u8, Output = [u8]>,
+    {
+        RegionWriter::new(self.index_mut(range))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn region_writer() {
+        let mut data = vec![0u8; 10];
+        {
+            let data = UnsafeCellSlice::new(&mut data);
+            let mut writer_a = unsafe { data.region_writer(..4) };
+            let mut writer_b = unsafe { data.region_writer(4..) };
+            write!(writer_a, "{}", 1234).unwrap();
+            assert_eq!(
+                writer_a.write(b"5").unwrap_err().kind(),
+                std::io::ErrorKind::WriteZero
+            );
+            writer_b.write_all(b"xyz").unwrap();
+            assert_eq!(writer_b.seek(SeekFrom::Current(-2)).unwrap(), 1);
+            writer_b.write_all(b"Y").unwrap();
+            assert_eq!(writer_b.position(), 2);
+            assert_eq!(writer_b.bytes_written(), 3);
+            assert_eq!(writer_b.seek(SeekFrom::End(-1)).unwrap(), 5);
+            assert_eq!(writer_b.write(b"ab").unwrap(), 1);
+            assert_eq!(writer_b.bytes_written(), 6);
+            assert!(writer_b.seek(SeekFrom::End(1)).is_err());
+            assert!(writer_b.seek(SeekFrom::Current(-7)).is_err());
+        }
+        assert_eq!(&data, b"1234xYz\0\0a");
+    }
+}
@@ -0,0 +1,304 @@
+use alloc::vec::Vec;
+use core::{
+    mem::MaybeUninit,
+    ptr::NonNull,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use crate::UnsafeCellSlice;
+
+/// A two-phase writer of variable-length items into one contiguous [`Vec`].
+///
+/// In the first phase, the item lengths are provided to the constructor which computes their offsets as an exclusive prefix sum and reserves the spare capacity of a [`Vec`].
+/// In the second phase, each item is independently written (e.g. in parallel) via an uninitialised mutable slice of exactly its length from [`item_mut`](JaggedWriter::item_mut).
+/// Finally, [`finish`](JaggedWriter::finish) returns the [`Vec`] and the item offsets.
+///
+/// The element type must be `'static`, since the items are borrowed from an [`UnsafeCellSlice`] of the spare capacity of the owned [`Vec`].
+///
+/// ```rust
+/// # use std::mem::MaybeUninit;
+/// # use rayon::prelude::*;
+/// # use unsafe_cell_slice::JaggedWriter;
+/// let lengths = vec![2, 0, 3];
+/// let writer = JaggedWriter::<u32>::new(&lengths);
+/// (0..lengths.len()).into_par_iter().for_each(|i| {
+///     writer.item_mut(i).fill(MaybeUninit::new(i as u32));
+/// });
+/// let (data, offsets) = unsafe { writer.finish() };
+/// assert_eq!(data, [0, 0, 2, 2, 2]);
+/// assert_eq!(offsets, [0, 2, 2, 5]);
+/// ```
+pub struct JaggedWriter<T: 'static> {
+    vec: Vec<T>,
+    spare: UnsafeCellSlice<'static, MaybeUninit<T>>,
+    offsets: Vec<usize>,
+    claimed: Vec<AtomicBool>,
+}
+
+// Items are only accessed mutably by one thread at a time, like a `Vec<T>` split into `&mut [T]`, so `T: Sync` is not required.
+unsafe impl<T: Send> Send for JaggedWriter<T> {}
+unsafe impl<T: Send> Sync for JaggedWriter<T> {}
+
+// The elements are not formatted, since `T: Sync` is not required to share a `JaggedWriter` between threads.
+impl<T> core::fmt::Debug for JaggedWriter<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("JaggedWriter")
+            .field("offsets", &self.offsets)
+            .field("claimed", &self.claimed)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> JaggedWriter<T> {
+    /// Create a new [`JaggedWriter`] for items with `lengths`.
+    ///
+    /// # Panics
+    /// Panics if the total length overflows a [`usize`].
+    #[must_use]
+    pub fn new(lengths: &[usize]) -> Self {
+        Self::with_vec(Vec::new(), lengths)
+    }
+
+    /// Create a new [`JaggedWriter`] for items with `lengths` that are appended to `vec`.
+    ///
+    /// # Panics
+    /// Panics if the total length overflows a [`usize`].
+    #[must_use]
+    pub fn with_vec(vec: Vec<T>, lengths: &[usize]) -> Self {
+        let offsets = exclusive_prefix_sum(vec.len(), lengths);
+        Self::from_offsets(vec, offsets)
+    }
+
+    /// Create a new [`JaggedWriter`] for items with `lengths`, computing the offsets in parallel.
+    ///
+    /// # Panics
+    /// Panics if the total length overflows a [`usize`].
+    #[cfg(feature = "rayon")]
+    #[must_use]
+    pub fn new_par(lengths: &[usize]) -> Self {
+        Self::with_vec_par(Vec::new(), lengths)
+    }
+
+    /// Create a new [`JaggedWriter`] for items with `lengths` that are appended to `vec`, computing the offsets in parallel.
+    ///
+    /// # Panics
+    /// Panics if the total length overflows a [`usize`].
+    #[cfg(feature = "rayon")]
+    #[must_use]
+    pub fn with_vec_par(vec: Vec<T>, lengths: &[usize]) -> Self {
+        let offsets = exclusive_prefix_sum_par(vec.len(), lengths);
+        Self::from_offsets(vec, offsets)
+    }
+
+    fn from_offsets(mut vec: Vec<T>, offsets: Vec<usize>) -> Self {
+        let len = offsets[offsets.len() - 1];
+        vec.reserve_exact(len - vec.len());
+        let spare = UnsafeCellSlice::from_spare_capacity(&mut vec);
+        // SAFETY: the spare capacity is on the heap so it does not move with `vec`, and `vec` is not accessed until `finish`
+        let spare = unsafe {
+            UnsafeCellSlice::from_raw_parts(
+                NonNull::new_unchecked(spare.as_mut_ptr().cast::<MaybeUninit<T>>()),
+                spare.len(),
+            )
+        };
+        let claimed = (1..offsets.len()).map(|_| AtomicBool::new(false)).collect();
+        Self {
+            vec,
+            spare,
+            offsets,
+            claimed,
+        }
+    }
+
+    /// Return the number of items.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.claimed.len()
+    }
+
+    /// Return whether there are no items.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.claimed.is_empty()
+    }
+
+    /// Return the item offsets in the output [`Vec`].
+    ///
+    /// Item `i` spans `offsets[i]..offsets[i + 1]`.
+    #[must_use]
+    pub fn offsets(&self) -> &[usize] {
+        &self.offsets
+    }
+
+    /// Get an uninitialised mutable slice of item `index`.
+    ///
+    /// Returns `None` if `index` is out of bounds or the item has already been requested.
+    #[must_use]
+    #[allow(clippy::mut_from_ref)]
+    pub fn try_item_mut(&self, index: usize) -> Option<&mut [MaybeUninit<T>]> {
+        if self.claimed.get(index)?.swap(true, Ordering::Relaxed) {
+            return None;
+        }
+        let start = self.offsets[index] - self.vec.len();
+        let end = self.offsets[index + 1] - self.vec.len();
+        // SAFETY: each item is only requested once and items do not overlap
+        Some(unsafe { self.spare.index_mut(start..end) })
+    }
+
+    /// Get an uninitialised mutable slice of item `index`.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds or the item has already been requested.
+    #[must_use]
+    #[allow(clippy::mut_from_ref)]
+    pub fn item_mut(&self, index: usize) -> &mut [MaybeUninit<T>] {
+        self.try_item_mut(index)
+            .expect("item index is out of bounds or has already been requested")
+    }
+
+    /// Finish writing, returning the output [`Vec`] and the item offsets.
+    ///
+    /// The offsets have one more element than the number of items, such that item `i` spans `offsets[i]..offsets[i + 1]`.
+    ///
+    /// # Safety
+    /// Every element of every item must have been initialised.
+    #[must_use]
+    pub unsafe fn finish(self) -> (Vec<T>, Vec<usize>) {
+        let Self {
+            mut vec, offsets, ..
+        } = self;
+        vec.set_len(offsets[offsets.len() - 1]);
+        (vec, offsets)
+    }
+}
+
+/// Return the exclusive prefix sum of `lengths` starting from `start`, including the total.
+pub(crate) fn exclusive_prefix_sum(start: usize, lengths: &[usize]) -> Vec<usize> {
+    let mut offsets = Vec::with_capacity(lengths.len() + 1);
+    let mut offset = start;
+    offsets.push(offset);
+    for length in lengths {
+        offset = offset.checked_add(*length).expect("total length overflow");
+        offsets.push(offset);
+    }
+    offsets
+}
+
+/// Return the exclusive prefix sum of `lengths` starting from `start`, including the total, computed in parallel.
+#[cfg(feature = "rayon")]
+fn exclusive_prefix_sum_par(start: usize, lengths: &[usize]) -> Vec<usize> {
+    use rayon::prelude::*;
+
+    const CHUNK_SIZE: usize = 4096;
+    let chunk_sums: Vec<usize> = lengths
+        .par_chunks(CHUNK_SIZE)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .try_fold(0usize, |sum, length| sum.checked_add(*length))
+                .expect("total length overflow")
+        })
+        .collect();
+    let chunk_offsets = exclusive_prefix_sum(start, &chunk_sums);
+    let mut offsets = vec![0; lengths.len() + 1];
+    offsets[lengths.len()] = chunk_offsets[chunk_sums.len()];
+    offsets[..lengths.len()]
+        .par_chunks_mut(CHUNK_SIZE)
+        .zip(lengths.par_chunks(CHUNK_SIZE))
+        .zip(chunk_offsets.par_iter())
+        .for_each(|((offsets, lengths), offset)| {
+            let mut offset = *offset;
+            for (dst, length) in offsets.iter_mut().zip(lengths) {
+                *dst = offset;
+                offset += length;
+            }
+        });
+    offsets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jagged_writer() {
+        let writer = JaggedWriter::with_vec(vec![9u8], &[1, 3, 0, 2]);
+        assert_eq!(writer.len(), 4);
+        assert_eq!(writer.offsets(), [1, 2, 5, 5, 7]);
+        writer.item_mut(1).fill(MaybeUninit::new(1));
+        assert!(writer.try_item_mut(1).is_none());
+        assert!(writer.try_item_mut(4).is_none());
+        writer.item_mut(3).fill(MaybeUninit::new(3));
+        writer.item_mut(0)[0].write(0);
+        assert!(writer.item_mut(2).is_empty());
+        let (data, offsets) = unsafe { writer.finish() };
+        assert_eq!(data, [9, 0, 1, 1, 1, 3, 3]);
+        assert_eq!(offsets, [1, 2, 5, 5, 7]);
+    }
+
+    #[test]
+    fn jagged_writer_debug() {
+        let writer = JaggedWriter::<core::cell::Cell<u8>>::new(&[1]);
+        assert_eq!(
+            format!("{writer:?}"),
+            "JaggedWriter { offsets: [0, 1], claimed: [false], .. }"
+        );
+    }
+
+    #[test]
+    fn jagged_writer_empty() {
+        let writer = JaggedWriter::<u8>::new(&[]);
+        assert!(writer.is_empty());
+        let (data, offsets) = unsafe { writer.finish() };
+        assert!(data.is_empty());
+        assert_eq!(offsets, [0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn jagged_writer_twice() {
+        let writer = JaggedWriter::<u8>::new(&[1]);
+        let _a = writer.item_mut(0);
+        let _b = writer.item_mut(0);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn jagged_writer_par() {
+        use rayon::prelude::*;
+        let lengths: Vec<usize> = (0..100).map(|i| i % 7).collect();
+        let writer = JaggedWriter::<usize>::new_par(&lengths);
+        (0..lengths.len()).into_par_iter().for_each(|i| {
+            writer.item_mut(i).fill(MaybeUninit::new(i));
+        });
+        let (data, offsets) = unsafe { writer.finish() };
+        assert_eq!(offsets, exclusive_prefix_sum(0, &lengths));
+        for (i, item) in offsets.windows(2).enumerate() {
+            assert!(data[item[0]..item[1]].iter().all(|v| *v == i));
+        }
+
+        let mut vec = Vec::with_capacity(512);
+        vec.extend([usize::MAX; 3]);
+        let writer = JaggedWriter::with_vec_par(vec, &lengths);
+        assert_eq!(writer.offsets()[0], 3);
+        (0..lengths.len()).into_par_iter().for_each(|i| {
+            writer.item_mut(i).fill(MaybeUninit::new(i));
+        });
+        let (data, offsets) = unsafe { writer.finish() };
+        assert_eq!(data[..3], [usize::MAX; 3]);
+        assert_eq!(offsets, exclusive_prefix_sum(3, &lengths));
+        for (i, item) in offsets.windows(2).enumerate() {
+            assert!(data[item[0]..item[1]].iter().all(|v| *v == i));
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn exclusive_prefix_sum_par() {
+        let lengths: Vec<usize> = (0..10_000).map(|i| i % 13).collect();
+        assert_eq!(
+            super::exclusive_prefix_sum_par(5, &lengths),
+            exclusive_prefix_sum(5, &lengths)
+        );
+    }
+}
@@ -38,7 +38,27 @@
 //! Note that this is very unsafe and bypasses Rust's safety guarantees!
 //! It is the responsibility of the caller of [`UnsafeCellSlice`] methods to avoid data races and undefined behavior by not requesting overlapping subslices/elements.
 //!
-//! Under the hood, [`UnsafeCellSlice`] is a reference to a [`std::cell::UnsafeCell`] slice, hence the name of the crate.
+//! Under the hood, [`UnsafeCellSlice`] is a [`NonNull`] pointer and a length with a [`PhantomData`] of the borrowed `&mut [T]`.
+//! Mutable references are derived from the raw pointer, so it behaves like a slice of [`core::cell::UnsafeCell`] (hence the name of the crate) without retagging the original borrow.
+//!
+//! ## Crate Features
+//! The core functionality of this crate only depends on [`core`], so it can be used in `no_std` environments by disabling default features.
+//!  - `std` (default): Enable [`std`] functionality, such as [`RegionWriter`], positional file reads and writes with `read_ranges_into` and `write_ranges_from` on Unix, and [`std::error::Error`] implementations. Implies `alloc`.
+//!  - `alloc`: Enable functionality requiring allocation, such as [`UnsafeCellSlice::new_from_vec_with_spare_capacity`], [`JaggedWriter`], [`StringWriter`], and [`UnsafeCellNested`].
+//!  - `bytemuck`: Enable `UnsafeCellSlice::cast_bytemuck` for reinterpreting slices of any [`bytemuck::Pod`](https://docs.rs/bytemuck/latest/bytemuck/trait.Pod.html) type.
+//!  - `derive`: Enable the [`UnsafeCellSoA`](https://docs.rs/unsafe_cell_slice_derive/latest/unsafe_cell_slice_derive/derive.UnsafeCellSoA.html) derive macro for struct-of-arrays views.
+//!  - `image`: Implement conversion from a mutable [`image::ImageBuffer`](https://docs.rs/image/latest/image/struct.ImageBuffer.html) to [`UnsafeCellSlice2D`].
+//!  - `memmap2`: Enable `MmapCellFile` for writing files through a memory mapping.
+//!  - `ndarray`: Enable `NdCellView` for N-dimensional access to an [`ndarray::ArrayViewMut`](https://docs.rs/ndarray/latest/ndarray/type.ArrayViewMut.html).
+//!  - `rayon`: Enable parallel offset computation in [`JaggedWriter`].
+//!  - `shm`: Enable `SharedCellBuffer` for sharing cell slices between processes on Linux.
+//!  - `smallvec`: Implement [`CellStorage`] for [`smallvec::SmallVec`](https://docs.rs/smallvec/latest/smallvec/struct.SmallVec.html).
+//!
+//! ## Model Checking
+//! Building with `RUSTFLAGS="--cfg loom"` records each reference acquired through [`SliceIndex`], [`Channel`], and the byte accessors of [`UnsafeCellSlice<u8>`](UnsafeCellSlice) as an access of a [`loom`](https://docs.rs/loom) `UnsafeCell`.
+//! [`loom::model`](https://docs.rs/loom/latest/loom/fn.model.html) then reports overlapping references acquired by concurrent threads where at least one is mutable.
+//! Accesses are tracked when references are acquired, not when they are used, and the tracked accesses of a region are discarded when a new [`UnsafeCellSlice`] is constructed over it.
+//! Functions that spawn their own threads, such as `read_ranges_into` and `write_ranges_from`, must not be called within a loom model.
 //!
 //! ## Licence
 //! `unsafe_cell_slice` is licensed under either of
@@ -47,39 +67,159 @@
 //!
 //! Unless you explicitly state otherwise, any contribution intentionally submitted for inclusion in the work by you, as defined in the Apache-2.0 license, shall be dual licensed as above, without any additional terms or conditions.
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(all(any(test, loom), not(feature = "std")))]
+#[cfg_attr(test, macro_use)]
+extern crate std;
+
+use core::{marker::PhantomData, ptr::NonNull};
+
+mod bytes;
+mod channels;
+mod chunks;
+mod const_index;
+#[cfg(all(feature = "std", unix))]
+mod file;
+#[cfg(feature = "std")]
+mod io;
+#[cfg(feature = "alloc")]
+mod jagged;
+#[cfg(loom)]
+mod loom;
+mod mapped;
+#[cfg(feature = "memmap2")]
+mod mmap;
+mod morton;
+#[cfg(feature = "ndarray")]
+mod nd;
+#[cfg(feature = "alloc")]
+mod nested;
+mod pod;
+mod ring;
+#[cfg(all(feature = "shm", target_os = "linux"))]
+mod shm;
+mod slice_2d;
 mod slice_index;
+mod soa;
+mod storage;
+#[cfg(feature = "alloc")]
+mod string;
 
-pub use slice_index::SliceIndex;
+pub use bytes::{Endian, Primitive};
+pub use channels::Channel;
+pub use const_index::{ConstIndex, ConstRange, ConstSliceIndex, ConstSliceIndices};
+#[cfg(all(feature = "std", unix))]
+pub use file::{read_ranges_into, write_ranges_from, FileRangesError};
+#[cfg(feature = "std")]
+pub use io::RegionWriter;
+#[cfg(feature = "alloc")]
+pub use jagged::JaggedWriter;
+pub use mapped::{IndexMap, IndexMapError, MappedSlice, Offset, Reverse, Stride, Table, Then};
+#[cfg(feature = "memmap2")]
+pub use mmap::{MmapCellFile, MmapCellFileFinishError};
+pub use morton::{
+    hilbert_decode_2d, hilbert_encode_2d, morton_decode_2d, morton_decode_3d, morton_encode_2d,
+    morton_encode_3d, HilbertGrid2D, MortonGrid2D, MortonGrid3D,
+};
+#[cfg(feature = "ndarray")]
+pub use nd::NdCellView;
+#[cfg(feature = "alloc")]
+pub use nested::UnsafeCellNested;
+pub use pod::{CastError, Pod};
+pub use ring::{RingIndex, UnsafeCellRing};
+#[cfg(all(feature = "shm", target_os = "linux"))]
+pub use shm::{SharedCellBuffer, SharedCellBufferError};
+pub use slice_2d::{TileMut, Tiles, UnsafeCellSlice2D};
+pub use slice_index::{IndexError, SliceIndex};
+pub use soa::{SoAField, SoALengthError};
+pub use storage::CellStorage;
+#[cfg(feature = "alloc")]
+pub use string::{StrRegion, StringWriter, StringWriterError};
+#[cfg(feature = "derive")]
+pub use unsafe_cell_slice_derive::UnsafeCellSoA;
 
 /// An unsafe cell slice. Permits acquisition of multiple mutable references of a slice.
 ///
 /// This is inherently unsafe.
 /// It is the responsibility of the caller to only access non-overlapping subslices/elements to avoid data races and undefined behavior.
-#[derive(Copy, Clone)]
-pub struct UnsafeCellSlice<'a, T>(&'a [std::cell::UnsafeCell<T>]);
+///
+/// ## Thread Safety
+/// An [`UnsafeCellSlice`] only hands out mutable references, so it is [`Send`] and [`Sync`] if `T` is [`Send`], like a `&mut [T]` split across threads.
+/// Thus, it can be used with `Send + !Sync` element types such as [`Cell`](core::cell::Cell):
+/// ```rust
+/// # use std::cell::Cell;
+/// # use unsafe_cell_slice::UnsafeCellSlice;
+/// let mut data = vec![Cell::new(0u8); 2];
+/// let data = UnsafeCellSlice::new(&mut data);
+/// std::thread::scope(|s| {
+///     s.spawn(|| unsafe { data.index_mut(0) }.set(1));
+///     s.spawn(|| unsafe { data.index_mut(1) }.set(2));
+/// });
+/// ```
+///
+/// It is neither [`Send`] nor [`Sync`] if `T` is not [`Send`]:
+/// ```rust,compile_fail
+/// # use std::rc::Rc;
+/// # use unsafe_cell_slice::UnsafeCellSlice;
+/// fn assert_sync<T: Sync>(_: &T) {}
+/// let mut data = vec![Rc::new(0u8); 2];
+/// assert_sync(&UnsafeCellSlice::new(&mut data));
+/// ```
+pub struct UnsafeCellSlice<'a, T> {
+    // Mutable references are derived from this pointer rather than from shared references to cells, so they do not retag the original `&mut [T]` under Stacked or Tree Borrows.
+    ptr: NonNull<T>,
+    len: usize,
+    _marker: PhantomData<&'a mut [T]>,
+}
 
-unsafe impl<T: Send + Sync> Send for UnsafeCellSlice<'_, T> {}
-unsafe impl<T: Send + Sync> Sync for UnsafeCellSlice<'_, T> {}
+impl<T> Clone for UnsafeCellSlice<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for UnsafeCellSlice<'_, T> {}
+
+unsafe impl<T: Send> Send for UnsafeCellSlice<'_, T> {}
+unsafe impl<T: Send> Sync for UnsafeCellSlice<'_, T> {}
 
 impl<'a, T> UnsafeCellSlice<'a, T> {
     /// Create a new [`UnsafeCellSlice`] from a mutable slice.
     #[must_use]
     pub fn new(slice: &'a mut [T]) -> Self {
-        // Rust 1.76: std::ptr::from_mut::<[T]>(slice)
-        let ptr = slice as *mut [T] as *const [std::cell::UnsafeCell<T>];
-        Self(unsafe { &*ptr })
+        let len = slice.len();
+        #[cfg(loom)]
+        crate::loom::reset(slice.as_ptr(), len);
+        // SAFETY: the slice is exclusively borrowed for 'a
+        unsafe { Self::from_raw_parts(NonNull::from(slice).cast::<T>(), len) }
+    }
+
+    /// Create a new [`UnsafeCellSlice`] from a pointer and a length.
+    ///
+    /// # Safety
+    /// `ptr` must be valid for reads and writes of `len` elements for `'a`, and the elements must not be accessed other than through the returned [`UnsafeCellSlice`] for `'a`.
+    pub(crate) unsafe fn from_raw_parts(ptr: NonNull<T>, len: usize) -> Self {
+        Self {
+            ptr,
+            len,
+            _marker: PhantomData,
+        }
     }
 
     /// Create a new [`UnsafeCellSlice`] from the spare capacity in a [`Vec`].
+    #[cfg(feature = "alloc")]
     #[must_use]
-    pub fn new_from_vec_with_spare_capacity(vec: &'a mut Vec<T>) -> Self {
-        Self::new(unsafe { vec_spare_capacity_to_mut_slice(vec) })
+    pub fn new_from_vec_with_spare_capacity(vec: &'a mut alloc::vec::Vec<T>) -> Self {
+        Self::from_spare_capacity(vec)
     }
 
     /// Return the length of the underlying slice.
     #[must_use]
     pub fn len(&self) -> usize {
-        self.0.len()
+        self.len
     }
 
     /// Reutrn whether the underlying slice is empty.
@@ -106,7 +246,38 @@ impl<'a, T> UnsafeCellSlice<'a, T> {
 
     /// Get a mutable reference to a subslice or element of the underlying slice.
     ///
-    /// Note that unlike [`std::ops::IndexMut::index_mut`], `self` is not a mutable reference.
+    /// ```rust
+    /// # use unsafe_cell_slice::{IndexError, UnsafeCellSlice};
+    /// let mut data = vec![0u8; 4];
+    /// let data = UnsafeCellSlice::new(&mut data);
+    /// assert!(unsafe { data.try_get_mut(1..4) }.is_ok());
+    /// assert_eq!(
+    ///     unsafe { data.try_get_mut(2..5) }.unwrap_err(),
+    ///     IndexError::EndOutOfRange { end: 5, len: 4 }
+    /// );
+    /// assert_eq!(
+    ///     unsafe { data.try_get_mut(4) }.unwrap_err().to_string(),
+    ///     "index out of bounds: the len is 4 but the index is 4"
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an [`IndexError`] if the index is out of bounds.
+    ///
+    /// # Safety
+    /// This is very unsafe because it is capable of creating multiple mutable references to the same data.
+    /// It is the responsibility of the caller to only access non-overlapping subslices to avoid data races and undefined behavior.
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn try_get_mut<I>(&self, index: I) -> Result<&mut I::Output, IndexError>
+    where
+        I: SliceIndex<T>,
+    {
+        index.try_get_mut(self)
+    }
+
+    /// Get a mutable reference to a subslice or element of the underlying slice.
+    ///
+    /// Note that unlike [`core::ops::IndexMut::index_mut`], `self` is not a mutable reference.
     /// Thus, this method does not support desuraging.
     ///
     /// # Safety
@@ -123,25 +294,102 @@ impl<'a, T> UnsafeCellSlice<'a, T> {
     {
         index.index_mut(self)
     }
-}
 
-/// Get a mutable slice of the spare capacity in a vector.
-///
-/// # Safety
-/// Returned elements are uninitialised.
-#[allow(unused_unsafe)]
-unsafe fn vec_spare_capacity_to_mut_slice<T>(vec: &mut Vec<T>) -> &mut [T] {
-    let spare_capacity = vec.spare_capacity_mut();
-    unsafe {
-        std::slice::from_raw_parts_mut(
-            spare_capacity.as_mut_ptr().cast::<T>(),
-            spare_capacity.len(),
-        )
+    /// Get mutable references to compile-time elements and/or subslices of the underlying slice.
+    ///
+    /// `I` is a [`ConstIndex`], a [`ConstRange`], or a tuple of those.
+    /// The indices in a tuple are checked to be disjoint at compile time, so the returned references do not alias each other.
+    /// Only the slice length is checked at runtime.
+    ///
+    /// Returns `None` if any index is out of bounds.
+    ///
+    /// ```rust
+    /// # use unsafe_cell_slice::{ConstIndex, ConstRange, UnsafeCellSlice};
+    /// let mut rgba = [0u8; 4];
+    /// let rgba = UnsafeCellSlice::new(&mut rgba);
+    /// let (rgb, a) = unsafe { rgba.get_const_mut::<(ConstRange<0, 3>, ConstIndex<3>)>() }.unwrap();
+    /// rgb.fill(255);
+    /// *a = 128;
+    /// ```
+    ///
+    /// Overlapping indices fail to compile:
+    /// ```rust,compile_fail
+    /// # use unsafe_cell_slice::{ConstIndex, ConstRange, UnsafeCellSlice};
+    /// let mut rgba = [0u8; 4];
+    /// let rgba = UnsafeCellSlice::new(&mut rgba);
+    /// let (rgb, a) = unsafe { rgba.get_const_mut::<(ConstRange<0, 3>, ConstIndex<2>)>() }.unwrap();
+    /// ```
+    ///
+    /// # Safety
+    /// This is very unsafe because it is capable of creating multiple mutable references to the same data.
+    /// It is the responsibility of the caller to not access the requested subslices/elements through any other reference.
+    #[must_use]
+    pub unsafe fn get_const_mut<'b, I>(&'b self) -> Option<I::Output>
+    where
+        I: ConstSliceIndices<'b, T>,
+    {
+        #[allow(clippy::let_unit_value)]
+        let () = I::DISJOINT;
+        if I::END <= self.len() {
+            Some(I::index_mut(self))
+        } else {
+            None
+        }
+    }
+
+    /// Get mutable references to compile-time elements and/or subslices of the underlying slice.
+    ///
+    /// See [`get_const_mut`](UnsafeCellSlice::get_const_mut).
+    ///
+    /// # Safety
+    /// This is very unsafe because it is capable of creating multiple mutable references to the same data.
+    /// It is the responsibility of the caller to not access the requested subslices/elements through any other reference.
+    ///
+    /// # Panics
+    /// Panics if any index is out of bounds.
+    #[must_use]
+    pub unsafe fn index_const_mut<'b, I>(&'b self) -> I::Output
+    where
+        I: ConstSliceIndices<'b, T>,
+    {
+        self.get_const_mut::<I>().expect("index out of bounds")
+    }
+
+    /// Return a raw pointer to the start of the underlying slice.
+    pub(crate) fn as_mut_ptr(&self) -> *mut T {
+        self.ptr.as_ptr()
+    }
+
+    /// Return the subslice `start..end` of the underlying slice.
+    ///
+    /// # Panics
+    /// Panics if the range is out of bounds.
+    pub(crate) fn subslice(&self, start: usize, end: usize) -> Self {
+        if let Err(err) = slice_index::check_range(start, end, self.len) {
+            panic!("{err}");
+        }
+        // SAFETY: the subslice is within the underlying slice
+        unsafe {
+            Self::from_raw_parts(
+                NonNull::new_unchecked(self.as_mut_ptr().add(start)),
+                end - start,
+            )
+        }
+    }
+
+    /// Reinterpret the underlying slice as `len` elements of type `U`.
+    ///
+    /// # Safety
+    /// The start of the underlying slice must be aligned for `U`, the `len` elements must not extend beyond the underlying slice, and any bit pattern of the underlying slice must be valid for `U` and vice versa.
+    pub(crate) unsafe fn cast_unchecked<U>(&self, len: usize) -> UnsafeCellSlice<'a, U> {
+        UnsafeCellSlice::from_raw_parts(self.ptr.cast::<U>(), len)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::vec::Vec;
+
     #[test]
     fn empty() {
         {
@@ -0,0 +1,46 @@
+//! Loom model-checking support, enabled with `--cfg loom`.
+//!
+//! An [`UnsafeCellSlice`](crate::UnsafeCellSlice) views memory owned elsewhere, so it cannot store [`loom::cell::UnsafeCell`]s inline.
+//! Instead, each byte of an accessed element is shadowed by a [`loom::cell::UnsafeCell`] in a per-execution side table, and acquiring a reference is recorded as an access of the shadow cells.
+//! Loom then reports acquisitions of overlapping elements by concurrent threads where at least one is mutable.
+//!
+//! The shadow cells of a region are discarded when a new [`UnsafeCellSlice`](crate::UnsafeCellSlice) is constructed over it, so reused memory does not inherit the accesses of a previous allocation.
+//! Accesses are only tracked while acquiring references in this crate, so accesses through references that outlive the construction of another slice over the same memory are not checked.
+
+use std::{collections::HashMap, sync::Mutex};
+
+use loom::cell::UnsafeCell;
+
+loom::lazy_static! {
+    static ref CELLS: Mutex<HashMap<usize, UnsafeCell<()>>> = Mutex::new(HashMap::new());
+}
+
+/// Call `f` with the shadow cell of each byte of `len` elements starting at `ptr`.
+fn for_each_cell<T>(ptr: *const T, len: usize, f: impl Fn(&UnsafeCell<()>)) {
+    let start = ptr as usize;
+    let size = core::mem::size_of::<T>() * len;
+    let mut cells = CELLS.lock().expect("loom cell table is not poisoned");
+    for address in start..start + size {
+        f(cells.entry(address).or_insert_with(|| UnsafeCell::new(())));
+    }
+}
+
+/// Record a mutable access of `len` elements starting at `ptr`.
+pub(crate) fn track_mut<T>(ptr: *const T, len: usize) {
+    for_each_cell(ptr, len, |cell| cell.with_mut(|_| ()));
+}
+
+/// Record a shared access of `len` elements starting at `ptr`.
+pub(crate) fn track_ref<T>(ptr: *const T, len: usize) {
+    for_each_cell(ptr, len, |cell| cell.with(|_| ()));
+}
+
+/// Discard the shadow cells of `len` elements starting at `ptr`, which are now owned by a new slice.
+pub(crate) fn reset<T>(ptr: *const T, len: usize) {
+    let start = ptr as usize;
+    let size = core::mem::size_of::<T>() * len;
+    let mut cells = CELLS.lock().expect("loom cell table is not poisoned");
+    for address in start..start + size {
+        cells.remove(&address);
+    }
+}
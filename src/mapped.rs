@@ -0,0 +1,445 @@
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
+
+use crate::UnsafeCellSlice;
+
+/// A mapping from logical indices `0..len` to indices of an underlying slice.
+///
+/// Maps can be composed with [`then`](IndexMap::then).
+///
+/// # Safety
+/// [`map`](IndexMap::map) must be deterministic.
+/// If [`is_injective`](IndexMap::is_injective) returns `true`, [`map`](IndexMap::map) must return distinct indices for distinct logical indices in `0..len`, except that any number of logical indices may map to [`usize::MAX`].
+pub unsafe trait IndexMap {
+    /// Return the number of logical indices.
+    fn len(&self) -> usize;
+
+    /// Return whether there are no logical indices.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Map a logical index in `0..len` to an index of the underlying slice.
+    ///
+    /// The result is bounds checked by [`MappedSlice`].
+    /// [`usize::MAX`] is never a valid index, so it can be returned for a logical index without a mapping.
+    fn map(&self, index: usize) -> usize;
+
+    /// Return `true` if the map is known to be injective, so [`MappedSlice::new_checked`] can skip the injectivity check.
+    fn is_injective(&self) -> bool {
+        false
+    }
+
+    /// Compose this map with `next`, mapping logical index `i` to `next.map(self.map(i))`.
+    ///
+    /// Indices mapped outside of `0..next.len()` map to [`usize::MAX`], so they are out of bounds.
+    #[must_use]
+    fn then<M: IndexMap>(self, next: M) -> Then<Self, M>
+    where
+        Self: Sized,
+    {
+        Then(self, next)
+    }
+}
+
+/// A map reversing the order of `len` indices.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Reverse {
+    len: usize,
+}
+
+impl Reverse {
+    /// Create a map from `i` to `len - 1 - i`.
+    #[must_use]
+    pub fn new(len: usize) -> Self {
+        Self { len }
+    }
+}
+
+unsafe impl IndexMap for Reverse {
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn map(&self, index: usize) -> usize {
+        self.len - 1 - index
+    }
+
+    fn is_injective(&self) -> bool {
+        true
+    }
+}
+
+/// A map offsetting `len` indices.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Offset {
+    offset: usize,
+    len: usize,
+}
+
+impl Offset {
+    /// Create a map from `i` to `offset + i`.
+    ///
+    /// # Panics
+    /// Panics if `offset + len` overflows a [`usize`].
+    #[must_use]
+    pub fn new(offset: usize, len: usize) -> Self {
+        assert!(offset.checked_add(len).is_some(), "offset overflow");
+        Self { offset, len }
+    }
+}
+
+unsafe impl IndexMap for Offset {
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn map(&self, index: usize) -> usize {
+        self.offset + index
+    }
+
+    fn is_injective(&self) -> bool {
+        true
+    }
+}
+
+/// A map selecting `len` indices separated by a stride.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Stride {
+    start: usize,
+    stride: usize,
+    len: usize,
+}
+
+impl Stride {
+    /// Create a map from `i` to `start + i * stride`.
+    ///
+    /// # Panics
+    /// Panics if `stride` is zero or the last index overflows a [`usize`].
+    #[must_use]
+    pub fn new(start: usize, stride: usize, len: usize) -> Self {
+        assert!(stride != 0, "stride must be non-zero");
+        assert!(
+            len.saturating_sub(1)
+                .checked_mul(stride)
+                .and_then(|last| last.checked_add(start))
+                .is_some(),
+            "stride overflow"
+        );
+        Self { start, stride, len }
+    }
+}
+
+unsafe impl IndexMap for Stride {
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn map(&self, index: usize) -> usize {
+        self.start + index * self.stride
+    }
+
+    fn is_injective(&self) -> bool {
+        true
+    }
+}
+
+/// A map looking up indices in a table, such as a `Vec<usize>` or `&[usize]`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Table<I>(pub I);
+
+unsafe impl<I: AsRef<[usize]>> IndexMap for Table<I> {
+    fn len(&self) -> usize {
+        self.0.as_ref().len()
+    }
+
+    fn map(&self, index: usize) -> usize {
+        self.0.as_ref()[index]
+    }
+}
+
+/// The composition of two maps, created by [`IndexMap::then`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Then<A, B>(A, B);
+
+unsafe impl<A: IndexMap, B: IndexMap> IndexMap for Then<A, B> {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn map(&self, index: usize) -> usize {
+        let index = self.0.map(index);
+        if index < self.1.len() {
+            self.1.map(index)
+        } else {
+            usize::MAX
+        }
+    }
+
+    fn is_injective(&self) -> bool {
+        // Indices outside of the domain of the second map all map to usize::MAX, which is exempt from injectivity
+        self.0.is_injective() && self.1.is_injective()
+    }
+}
+
+/// An error creating a [`MappedSlice`] with [`MappedSlice::new_checked`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum IndexMapError {
+    /// A logical index maps outside of the underlying slice.
+    OutOfBounds {
+        /// The logical index.
+        index: usize,
+        /// The mapped index.
+        mapped: usize,
+        /// The length of the underlying slice.
+        len: usize,
+    },
+    /// Two logical indices map to the same index of the underlying slice.
+    NotInjective {
+        /// The first logical index.
+        first: usize,
+        /// The second logical index.
+        second: usize,
+        /// The mapped index.
+        mapped: usize,
+    },
+}
+
+impl core::fmt::Display for IndexMapError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::OutOfBounds { index, mapped, len } => write!(
+                f,
+                "index {index} maps to {mapped} which is out of bounds for slice of length {len}"
+            ),
+            Self::NotInjective {
+                first,
+                second,
+                mapped,
+            } => write!(f, "indices {first} and {second} both map to {mapped}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for IndexMapError {}
+
+/// An [`UnsafeCellSlice`] view with logical indices mapped by an [`IndexMap`]. Permits acquisition of multiple mutable references of elements in mapped order.
+///
+/// This is inherently unsafe.
+/// It is the responsibility of the caller to only access non-overlapping elements to avoid data races and undefined behavior.
+pub struct MappedSlice<'a, T, M> {
+    data: UnsafeCellSlice<'a, T>,
+    map: M,
+}
+
+impl<T, M: Clone> Clone for MappedSlice<'_, T, M> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data,
+            map: self.map.clone(),
+        }
+    }
+}
+
+impl<T, M: Copy> Copy for MappedSlice<'_, T, M> {}
+
+impl<'a, T, M: IndexMap> MappedSlice<'a, T, M> {
+    /// Create a new [`MappedSlice`] without checking the map.
+    ///
+    /// Mapped indices are bounds checked on access, but distinct logical indices may alias the same element.
+    #[must_use]
+    pub fn new(data: UnsafeCellSlice<'a, T>, map: M) -> Self {
+        Self { data, map }
+    }
+
+    /// Create a new [`MappedSlice`], checking that every logical index maps to a distinct element of `data`.
+    ///
+    /// The injectivity check is skipped if [`IndexMap::is_injective`] returns `true`.
+    /// Distinct logical indices of the returned [`MappedSlice`] never alias the same element.
+    ///
+    /// ```rust
+    /// # use unsafe_cell_slice::{IndexMap, IndexMapError, MappedSlice, Reverse, Table, UnsafeCellSlice};
+    /// let mut data = vec![0u8; 4];
+    /// let slice = UnsafeCellSlice::new(&mut data);
+    /// let mapped = MappedSlice::new_checked(slice, Table([3, 1, 2]).then(Reverse::new(4))).unwrap();
+    /// *unsafe { mapped.index_mut(0) } = 1;
+    /// assert_eq!(
+    ///     MappedSlice::new_checked(slice, Table([0, 1, 0])).err(),
+    ///     Some(IndexMapError::NotInjective { first: 0, second: 2, mapped: 0 })
+    /// );
+    /// assert_eq!(data, [1, 0, 0, 0]);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an [`IndexMapError`] if a logical index maps out of bounds or two logical indices map to the same element.
+    #[cfg(feature = "alloc")]
+    pub fn new_checked(data: UnsafeCellSlice<'a, T>, map: M) -> Result<Self, IndexMapError> {
+        let len = data.len();
+        let check_injective = !map.is_injective();
+        let mut first = if check_injective {
+            vec![usize::MAX; len]
+        } else {
+            Vec::new()
+        };
+        for index in 0..map.len() {
+            let mapped = map.map(index);
+            if mapped == usize::MAX || mapped >= len {
+                return Err(IndexMapError::OutOfBounds { index, mapped, len });
+            }
+            if check_injective {
+                if first[mapped] != usize::MAX {
+                    return Err(IndexMapError::NotInjective {
+                        first: first[mapped],
+                        second: index,
+                        mapped,
+                    });
+                }
+                first[mapped] = index;
+            }
+        }
+        Ok(Self { data, map })
+    }
+
+    /// Return the number of logical indices.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Return whether there are no logical indices.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Return the index map.
+    #[must_use]
+    pub fn index_map(&self) -> &M {
+        &self.map
+    }
+
+    /// Return the underlying [`UnsafeCellSlice`].
+    #[must_use]
+    pub fn as_slice(&self) -> UnsafeCellSlice<'a, T> {
+        self.data
+    }
+
+    /// Get a mutable reference to the element at logical index `index`.
+    ///
+    /// Returns `None` if the index or mapped index is out of bounds.
+    ///
+    /// # Safety
+    /// This is very unsafe because it is capable of creating multiple mutable references to the same data.
+    /// It is the responsibility of the caller to only access non-overlapping elements to avoid data races and undefined behavior.
+    /// Distinct logical indices may map to the same element unless the [`MappedSlice`] was created with [`MappedSlice::new_checked`].
+    #[must_use]
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn get_mut(&self, index: usize) -> Option<&mut T> {
+        if index >= self.map.len() {
+            return None;
+        }
+        match self.map.map(index) {
+            usize::MAX => None,
+            mapped => self.data.get_mut(mapped),
+        }
+    }
+
+    /// Get a mutable reference to the element at logical index `index`.
+    ///
+    /// # Safety
+    /// This is very unsafe because it is capable of creating multiple mutable references to the same data.
+    /// It is the responsibility of the caller to only access non-overlapping elements to avoid data races and undefined behavior.
+    /// Distinct logical indices may map to the same element unless the [`MappedSlice`] was created with [`MappedSlice::new_checked`].
+    ///
+    /// # Panics
+    /// Panics if the index or mapped index is out of bounds.
+    #[must_use]
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn index_mut(&self, index: usize) -> &mut T {
+        self.get_mut(index).expect("index out of bounds")
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use std::vec::Vec;
+
+    #[test]
+    fn mapped_builtin() {
+        let mut data: Vec<usize> = (0..10).collect();
+        let slice = UnsafeCellSlice::new(&mut data);
+
+        let reverse = MappedSlice::new_checked(slice, Reverse::new(10)).unwrap();
+        assert_eq!(*unsafe { reverse.index_mut(0) }, 9);
+        assert!(unsafe { reverse.get_mut(10) }.is_none());
+
+        let offset = MappedSlice::new_checked(slice, Offset::new(7, 3)).unwrap();
+        assert_eq!(*unsafe { offset.index_mut(2) }, 9);
+        assert_eq!(
+            MappedSlice::new_checked(slice, Offset::new(8, 3)).err(),
+            Some(IndexMapError::OutOfBounds {
+                index: 2,
+                mapped: 10,
+                len: 10
+            })
+        );
+
+        let stride = MappedSlice::new_checked(slice, Stride::new(1, 3, 3)).unwrap();
+        assert_eq!(stride.len(), 3);
+        assert_eq!(*unsafe { stride.index_mut(2) }, 7);
+
+        let composed = Stride::new(0, 2, 5).then(Reverse::new(10));
+        assert!(composed.is_injective());
+        let composed = MappedSlice::new_checked(slice, composed).unwrap();
+        assert_eq!(*unsafe { composed.index_mut(1) }, 7);
+    }
+
+    #[test]
+    fn mapped_table() {
+        let mut data = vec![0u8; 4];
+        {
+            let slice = UnsafeCellSlice::new(&mut data);
+            let table = MappedSlice::new_checked(slice, Table(vec![2, 0, 3])).unwrap();
+            assert_eq!(table.index_map().len(), 3);
+            for i in 0..table.len() {
+                *unsafe { table.index_mut(i) } = i as u8 + 1;
+            }
+            assert!(MappedSlice::new_checked(slice, Table([1, 1])).is_err());
+            assert!(MappedSlice::new_checked(slice, Table([4])).is_err());
+
+            let unchecked = MappedSlice::new(slice, Table([9]));
+            assert!(unsafe { unchecked.get_mut(0) }.is_none());
+
+            let composed = Table([0, 5]).then(Reverse::new(4));
+            assert_eq!(composed.map(1), usize::MAX);
+            assert!(MappedSlice::new_checked(slice, composed).is_err());
+
+            let mut units = [(); usize::MAX];
+            let units = UnsafeCellSlice::new(&mut units);
+            let composed = Offset::new(0, 2).then(Offset::new(usize::MAX - 1, 1));
+            assert!(composed.is_injective());
+            assert!(MappedSlice::new_checked(units, composed).is_err());
+            let composed = MappedSlice::new(units, composed);
+            assert!(unsafe { composed.get_mut(0) }.is_some());
+            assert!(unsafe { composed.get_mut(1) }.is_none());
+        }
+        assert_eq!(data, [2, 0, 1, 3]);
+    }
+
+    #[test]
+    fn mapped_par() {
+        use rayon::prelude::*;
+        let mut data = vec![0usize; 100];
+        {
+            let slice = UnsafeCellSlice::new(&mut data);
+            let table: Vec<usize> = (0..100).map(|i| (i * 37) % 100).collect();
+            let mapped = MappedSlice::new_checked(slice, Table(table)).unwrap();
+            (0..mapped.len()).into_par_iter().for_each(|i| {
+                *unsafe { mapped.index_mut(i) } = i;
+            });
+        }
+        assert!(data.iter().enumerate().all(|(i, v)| (v * 37) % 100 == i));
+    }
+}
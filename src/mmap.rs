@@ -0,0 +1,232 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::{Error, ErrorKind},
+    path::Path,
+};
+
+use memmap2::{MmapMut, MmapOptions};
+
+use crate::{CastError, Pod, UnsafeCellSlice};
+
+/// A file that is written through a writable memory mapping.
+///
+/// The mapping is exposed as an [`UnsafeCellSlice<u8>`](UnsafeCellSlice), so threads can write disjoint byte ranges of the file with the same patterns as an in-memory buffer.
+///
+/// ```rust
+/// # use unsafe_cell_slice::MmapCellFile;
+/// # let path = std::env::temp_dir().join("unsafe_cell_slice_mmap_doctest.bin");
+/// let mut file = unsafe { MmapCellFile::create(&path, 8) }?;
+/// {
+///     let data = file.as_slice();
+///     std::thread::scope(|s| {
+///         s.spawn(|| unsafe { data.index_mut(..4) }.fill(1));
+///         s.spawn(|| unsafe { data.index_mut(4..) }.fill(2));
+///     });
+/// }
+/// file.finish(6)?;
+/// assert_eq!(std::fs::read(&path)?, [1, 1, 1, 1, 2, 2]);
+/// # std::fs::remove_file(&path)?;
+/// # Ok::<_, std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct MmapCellFile {
+    file: File,
+    mmap: MmapMut,
+}
+
+impl MmapCellFile {
+    /// Create a file of `len` zeroed bytes at `path` and map it for writing.
+    ///
+    /// An existing file at `path` is truncated.
+    ///
+    /// # Errors
+    /// Returns an [`io::Error`](std::io::Error) if the file cannot be created, resized, or mapped.
+    ///
+    /// # Safety
+    /// The file must not be modified or truncated by other processes or through other handles while it is mapped.
+    pub unsafe fn create(path: impl AsRef<Path>, len: usize) -> std::io::Result<Self> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        file.set_len(len as u64)?;
+        let mmap = MmapOptions::new().len(len).map_mut(&file)?;
+        Ok(Self { file, mmap })
+    }
+
+    /// Return the length of the file in bytes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.mmap.len()
+    }
+
+    /// Return whether the file is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return an [`UnsafeCellSlice`] of the bytes of the file.
+    #[must_use]
+    pub fn as_slice(&mut self) -> UnsafeCellSlice<'_, u8> {
+        UnsafeCellSlice::new(&mut self.mmap)
+    }
+
+    /// Return an [`UnsafeCellSlice`] of the file reinterpreted as a slice of [`Pod`] elements.
+    ///
+    /// The mapping starts at a page boundary, so it is aligned for any primitive type.
+    ///
+    /// # Errors
+    /// Returns a [`CastError`] if the length of the file is not a multiple of the size of `U`.
+    pub fn cast<U: Pod>(&mut self) -> Result<UnsafeCellSlice<'_, U>, CastError> {
+        self.as_slice().cast::<U>()
+    }
+
+    /// Flush `len` bytes starting at `offset` to the file.
+    ///
+    /// # Errors
+    /// Returns an [`io::Error`](std::io::Error) if the range is out of bounds or the flush fails.
+    pub fn flush_range(&self, offset: usize, len: usize) -> std::io::Result<()> {
+        match offset.checked_add(len) {
+            Some(end) if end <= self.len() => self.mmap.flush_range(offset, len),
+            _ => Err(Error::new(
+                ErrorKind::InvalidInput,
+                "flush range is out of bounds",
+            )),
+        }
+    }
+
+    /// Flush all bytes to the file.
+    ///
+    /// # Errors
+    /// Returns an [`io::Error`](std::io::Error) if the flush fails.
+    pub fn flush(&self) -> std::io::Result<()> {
+        self.mmap.flush()
+    }
+
+    /// Flush and unmap the file, truncate it to `len` bytes, and sync it to disk.
+    ///
+    /// # Errors
+    /// Returns a [`MmapCellFileFinishError`] if `len` exceeds the length of the file or the flush, truncation, or sync fails.
+    /// If `len` is invalid or the flush fails, the file is still mapped and can be recovered with [`MmapCellFileFinishError::into_file`].
+    pub fn finish(self, len: usize) -> Result<File, MmapCellFileFinishError> {
+        if len > self.len() {
+            return Err(MmapCellFileFinishError {
+                file: Some(self),
+                error: Error::new(
+                    ErrorKind::InvalidInput,
+                    "finished length exceeds the file length",
+                ),
+            });
+        }
+        if let Err(error) = self.mmap.flush() {
+            return Err(MmapCellFileFinishError {
+                file: Some(self),
+                error,
+            });
+        }
+        let Self { file, mmap } = self;
+        drop(mmap);
+        file.set_len(len as u64)
+            .and_then(|()| file.sync_all())
+            .map_err(|error| MmapCellFileFinishError { file: None, error })?;
+        Ok(file)
+    }
+}
+
+/// An error finishing a [`MmapCellFile`] with [`MmapCellFile::finish`].
+///
+/// It converts into an [`io::Error`](std::io::Error), so it can be propagated with `?` in functions returning [`io::Result`](std::io::Result).
+#[derive(Debug)]
+pub struct MmapCellFileFinishError {
+    file: Option<MmapCellFile>,
+    error: Error,
+}
+
+impl MmapCellFileFinishError {
+    /// Return the underlying [`io::Error`](std::io::Error).
+    #[must_use]
+    pub fn error(&self) -> &Error {
+        &self.error
+    }
+
+    /// Return the [`MmapCellFile`] if the error occurred before it was unmapped.
+    #[must_use]
+    pub fn into_file(self) -> Option<MmapCellFile> {
+        self.file
+    }
+}
+
+impl core::fmt::Display for MmapCellFileFinishError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl std::error::Error for MmapCellFileFinishError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl From<MmapCellFileFinishError> for Error {
+    fn from(err: MmapCellFileFinishError) -> Self {
+        err.error
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!(
+            "unsafe_cell_slice_{name}_{}.bin",
+            std::process::id()
+        ))
+    }
+
+    #[test]
+    fn mmap_cell_file() {
+        let path = temp_path("mmap_cell_file");
+        let mut file = unsafe { MmapCellFile::create(&path, 16) }.unwrap();
+        assert_eq!(file.len(), 16);
+        {
+            let data = file.cast::<u32>().unwrap();
+            assert_eq!(data.len(), 4);
+            unsafe { data.index_mut(..) }.copy_from_slice(&[1, 2, 3, 4]);
+        }
+        file.flush_range(0, 8).unwrap();
+        assert!(file.flush_range(8, 9).is_err());
+        assert_eq!(file.cast::<[u8; 3]>().err(), Some(CastError::Size));
+        let err = file.finish(17).unwrap_err();
+        assert_eq!(err.error().kind(), ErrorKind::InvalidInput);
+        let file = err.into_file().expect("the file is still mapped");
+        assert_eq!(file.len(), 16);
+        file.finish(16).unwrap();
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn mmap_cell_file_finish() {
+        let path = temp_path("mmap_cell_file_finish");
+        let mut file = unsafe { MmapCellFile::create(&path, 4096) }.unwrap();
+        unsafe { file.as_slice().index_mut(..3) }.copy_from_slice(b"abc");
+        let file = file.finish(3).unwrap();
+        assert_eq!(file.metadata().unwrap().len(), 3);
+        assert_eq!(std::fs::read(&path).unwrap(), b"abc");
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn mmap_cell_file_empty() {
+        let path = temp_path("mmap_cell_file_empty");
+        let mut file = unsafe { MmapCellFile::create(&path, 0) }.unwrap();
+        assert!(file.is_empty());
+        assert!(file.as_slice().is_empty());
+        file.finish(0).unwrap();
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,669 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use core::ops::Range;
+
+use crate::UnsafeCellSlice;
+
+/// Spread the bits of `x` so that bit `i` moves to bit `2i`.
+fn part_1_by_1(x: u32) -> u64 {
+    let mut x = u64::from(x);
+    x = (x | (x << 16)) & 0x0000_FFFF_0000_FFFF;
+    x = (x | (x << 8)) & 0x00FF_00FF_00FF_00FF;
+    x = (x | (x << 4)) & 0x0F0F_0F0F_0F0F_0F0F;
+    x = (x | (x << 2)) & 0x3333_3333_3333_3333;
+    (x | (x << 1)) & 0x5555_5555_5555_5555
+}
+
+/// Gather bit `2i` of `x` into bit `i`.
+#[allow(clippy::cast_possible_truncation)]
+fn compact_1_by_1(x: u64) -> u32 {
+    let mut x = x & 0x5555_5555_5555_5555;
+    x = (x ^ (x >> 1)) & 0x3333_3333_3333_3333;
+    x = (x ^ (x >> 2)) & 0x0F0F_0F0F_0F0F_0F0F;
+    x = (x ^ (x >> 4)) & 0x00FF_00FF_00FF_00FF;
+    x = (x ^ (x >> 8)) & 0x0000_FFFF_0000_FFFF;
+    ((x ^ (x >> 16)) & 0xFFFF_FFFF) as u32
+}
+
+/// Spread the low 21 bits of `x` so that bit `i` moves to bit `3i`.
+fn part_1_by_2(x: u32) -> u64 {
+    let mut x = u64::from(x) & 0x1F_FFFF;
+    x = (x | (x << 32)) & 0x001F_0000_0000_FFFF;
+    x = (x | (x << 16)) & 0x001F_0000_FF00_00FF;
+    x = (x | (x << 8)) & 0x100F_00F0_0F00_F00F;
+    x = (x | (x << 4)) & 0x10C3_0C30_C30C_30C3;
+    (x | (x << 2)) & 0x1249_2492_4924_9249
+}
+
+/// Gather bit `3i` of `x` into bit `i`.
+#[allow(clippy::cast_possible_truncation)]
+fn compact_1_by_2(x: u64) -> u32 {
+    let mut x = x & 0x1249_2492_4924_9249;
+    x = (x ^ (x >> 2)) & 0x10C3_0C30_C30C_30C3;
+    x = (x ^ (x >> 4)) & 0x100F_00F0_0F00_F00F;
+    x = (x ^ (x >> 8)) & 0x001F_0000_FF00_00FF;
+    x = (x ^ (x >> 16)) & 0x001F_0000_0000_FFFF;
+    ((x ^ (x >> 32)) & 0x1F_FFFF) as u32
+}
+
+/// Encode 2D coordinates as a Morton (Z-order) index by interleaving their bits, with `x` in the least significant bit.
+#[must_use]
+pub fn morton_encode_2d(x: u32, y: u32) -> u64 {
+    part_1_by_1(x) | (part_1_by_1(y) << 1)
+}
+
+/// Decode a Morton (Z-order) index into 2D coordinates.
+///
+/// This is the inverse of [`morton_encode_2d`].
+#[must_use]
+pub fn morton_decode_2d(index: u64) -> (u32, u32) {
+    (compact_1_by_1(index), compact_1_by_1(index >> 1))
+}
+
+/// Encode 3D coordinates as a Morton (Z-order) index by interleaving their bits, with `x` in the least significant bit.
+///
+/// Only the low 21 bits of each coordinate are encoded.
+#[must_use]
+pub fn morton_encode_3d(x: u32, y: u32, z: u32) -> u64 {
+    part_1_by_2(x) | (part_1_by_2(y) << 1) | (part_1_by_2(z) << 2)
+}
+
+/// Decode a Morton (Z-order) index into 3D coordinates.
+///
+/// This is the inverse of [`morton_encode_3d`].
+#[must_use]
+pub fn morton_decode_3d(index: u64) -> (u32, u32, u32) {
+    (
+        compact_1_by_2(index),
+        compact_1_by_2(index >> 1),
+        compact_1_by_2(index >> 2),
+    )
+}
+
+/// Rotate/flip a quadrant of side `side` for the Hilbert curve.
+fn hilbert_rotate(side: u64, x: &mut u64, y: &mut u64, rx: u64, ry: u64) {
+    if ry == 0 {
+        if rx == 1 {
+            *x = side - 1 - *x;
+            *y = side - 1 - *y;
+        }
+        core::mem::swap(x, y);
+    }
+}
+
+/// Encode 2D coordinates as a Hilbert curve index on a grid with side `2^order`.
+///
+/// # Panics
+/// Panics if `order` exceeds 32 or a coordinate is outside of the grid.
+#[must_use]
+pub fn hilbert_encode_2d(order: u32, x: u32, y: u32) -> u64 {
+    assert!(order <= 32, "Hilbert order must not exceed 32");
+    let side = 1u64 << order;
+    let (mut x, mut y) = (u64::from(x), u64::from(y));
+    assert!(x < side && y < side, "coordinates out of bounds");
+    let mut index = 0;
+    let mut s = side / 2;
+    while s > 0 {
+        let rx = u64::from(x & s > 0);
+        let ry = u64::from(y & s > 0);
+        index += s * s * ((3 * rx) ^ ry);
+        hilbert_rotate(side, &mut x, &mut y, rx, ry);
+        s /= 2;
+    }
+    index
+}
+
+/// Decode a Hilbert curve index on a grid with side `2^order` into 2D coordinates.
+///
+/// This is the inverse of [`hilbert_encode_2d`].
+///
+/// # Panics
+/// Panics if `order` exceeds 32 or the index is outside of the grid.
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub fn hilbert_decode_2d(order: u32, index: u64) -> (u32, u32) {
+    assert!(order <= 32, "Hilbert order must not exceed 32");
+    let side = 1u64 << order;
+    assert!(
+        order == 32 || index < side * side,
+        "Hilbert index out of bounds"
+    );
+    let (mut x, mut y) = (0, 0);
+    let mut t = index;
+    let mut s = 1;
+    while s < side {
+        let rx = 1 & (t / 2);
+        let ry = 1 & (t ^ rx);
+        hilbert_rotate(s, &mut x, &mut y, rx, ry);
+        x += s * rx;
+        y += s * ry;
+        t /= 4;
+        s *= 2;
+    }
+    (x as u32, y as u32)
+}
+
+/// Return the side of a square/cubic grid with `len` elements, if it is a power of two.
+fn grid_side(len: usize, side: usize, dims: u32) -> usize {
+    assert!(side.is_power_of_two(), "grid side must be a power of two");
+    assert!(
+        side.checked_pow(dims) == Some(len),
+        "slice length must equal the number of grid cells"
+    );
+    side
+}
+
+/// Append `run` to `runs`, merging it with the last run if contiguous.
+#[cfg(feature = "alloc")]
+fn push_run(runs: &mut Vec<Range<usize>>, run: Range<usize>) {
+    match runs.last_mut() {
+        Some(last) if last.end == run.start => last.end = run.end,
+        _ => runs.push(run),
+    }
+}
+
+/// Return whether `[start, start + size)` is inside/intersects `range`.
+#[cfg(feature = "alloc")]
+fn overlap(start: usize, size: usize, range: &Range<usize>) -> (bool, bool) {
+    let end = start + size;
+    let inside = range.start <= start && end <= range.end;
+    let intersects = start < range.end && range.start < end;
+    (inside, intersects)
+}
+
+/// A two-dimensional view of an [`UnsafeCellSlice`] stored in Morton (Z-order) order. Permits acquisition of multiple mutable references of cells by (x, y) coordinates.
+///
+/// The grid is square with a power of two side, and each quadrant is a contiguous quarter of the underlying slice.
+///
+/// This is inherently unsafe.
+/// It is the responsibility of the caller to only access non-overlapping subslices/elements to avoid data races and undefined behavior.
+pub struct MortonGrid2D<'a, T> {
+    data: UnsafeCellSlice<'a, T>,
+    side: usize,
+}
+
+impl<T> Clone for MortonGrid2D<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for MortonGrid2D<'_, T> {}
+
+impl<'a, T> MortonGrid2D<'a, T> {
+    /// Create a new [`MortonGrid2D`] with `side * side` cells.
+    ///
+    /// # Panics
+    /// Panics if `side` is not a power of two or the length of `data` is not `side * side`.
+    #[must_use]
+    pub fn new(data: UnsafeCellSlice<'a, T>, side: usize) -> Self {
+        let side = grid_side(data.len(), side, 2);
+        Self { data, side }
+    }
+
+    /// Return the side of the grid.
+    #[must_use]
+    pub fn side(&self) -> usize {
+        self.side
+    }
+
+    /// Return the underlying [`UnsafeCellSlice`] in Morton order.
+    #[must_use]
+    pub fn as_slice(&self) -> UnsafeCellSlice<'a, T> {
+        self.data
+    }
+
+    /// Return the linear offset of the cell at (`x`, `y`), or `None` if out of bounds.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn offset(&self, x: usize, y: usize) -> Option<usize> {
+        (x < self.side && y < self.side).then(|| morton_encode_2d(x as u32, y as u32) as usize)
+    }
+
+    /// Get a mutable reference to the cell at (`x`, `y`).
+    ///
+    /// Returns `None` if the cell is out of bounds.
+    ///
+    /// # Safety
+    /// This is very unsafe because it is capable of creating multiple mutable references to the same data.
+    /// It is the responsibility of the caller to only access non-overlapping subslices to avoid data races and undefined behavior.
+    #[must_use]
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn get_mut(&self, x: usize, y: usize) -> Option<&mut T> {
+        self.data.get_mut(self.offset(x, y)?)
+    }
+
+    /// Get a mutable reference to the cell at (`x`, `y`).
+    ///
+    /// # Safety
+    /// This is very unsafe because it is capable of creating multiple mutable references to the same data.
+    /// It is the responsibility of the caller to only access non-overlapping subslices to avoid data races and undefined behavior.
+    ///
+    /// # Panics
+    /// Panics if the cell is out of bounds.
+    #[must_use]
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn index_mut(&self, x: usize, y: usize) -> &mut T {
+        self.get_mut(x, y).expect("index out of bounds")
+    }
+
+    /// Return the contiguous runs of linear offsets covering the cells in a rectangular block, in increasing order.
+    ///
+    /// ```rust
+    /// # use unsafe_cell_slice::{MortonGrid2D, UnsafeCellSlice};
+    /// let mut data = vec![0u8; 16];
+    /// let grid = MortonGrid2D::new(UnsafeCellSlice::new(&mut data), 4);
+    /// assert_eq!(grid.block_runs(0..2, 0..4), [0..4, 8..12]);
+    /// assert_eq!(grid.block_runs(1..3, 0..1), [1..2, 4..5]);
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if the block is out of bounds.
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn block_runs(&self, x: Range<usize>, y: Range<usize>) -> Vec<Range<usize>> {
+        assert!(
+            x.end <= self.side && y.end <= self.side,
+            "block out of bounds"
+        );
+        let mut runs = Vec::new();
+        if !x.is_empty() && !y.is_empty() {
+            self.block_runs_impl(&x, &y, (0, 0), self.side, 0, &mut runs);
+        }
+        runs
+    }
+
+    #[cfg(feature = "alloc")]
+    fn block_runs_impl(
+        &self,
+        x: &Range<usize>,
+        y: &Range<usize>,
+        origin: (usize, usize),
+        side: usize,
+        offset: usize,
+        runs: &mut Vec<Range<usize>>,
+    ) {
+        let (x_inside, x_intersects) = overlap(origin.0, side, x);
+        let (y_inside, y_intersects) = overlap(origin.1, side, y);
+        if x_inside && y_inside {
+            push_run(runs, offset..offset + side * side);
+        } else if x_intersects && y_intersects {
+            let half = side / 2;
+            for child in 0..4 {
+                let origin = (
+                    origin.0 + (child & 1) * half,
+                    origin.1 + (child >> 1) * half,
+                );
+                self.block_runs_impl(x, y, origin, half, offset + child * half * half, runs);
+            }
+        }
+    }
+
+    /// Split the grid into its four quadrants in Morton order: (0, 0), (1, 0), (0, 1), and (1, 1).
+    ///
+    /// Each quadrant is a [`MortonGrid2D`] over a disjoint contiguous quarter of the underlying slice.
+    /// Returns `None` if the grid has a side of one.
+    #[must_use]
+    pub fn quadrants(&self) -> Option<[Self; 4]> {
+        let half = self.side / 2;
+        if half == 0 {
+            return None;
+        }
+        let data = self.data;
+        let len = half * half;
+        Some(core::array::from_fn(|child| Self {
+            data: data.subslice(child * len, (child + 1) * len),
+            side: half,
+        }))
+    }
+}
+
+/// A three-dimensional view of an [`UnsafeCellSlice`] stored in Morton (Z-order) order. Permits acquisition of multiple mutable references of cells by (x, y, z) coordinates.
+///
+/// The grid is cubic with a power of two side, and each octant is a contiguous eighth of the underlying slice.
+///
+/// This is inherently unsafe.
+/// It is the responsibility of the caller to only access non-overlapping subslices/elements to avoid data races and undefined behavior.
+pub struct MortonGrid3D<'a, T> {
+    data: UnsafeCellSlice<'a, T>,
+    side: usize,
+}
+
+impl<T> Clone for MortonGrid3D<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for MortonGrid3D<'_, T> {}
+
+impl<'a, T> MortonGrid3D<'a, T> {
+    /// Create a new [`MortonGrid3D`] with `side * side * side` cells.
+    ///
+    /// # Panics
+    /// Panics if `side` is not a power of two or the length of `data` is not `side * side * side`.
+    #[must_use]
+    pub fn new(data: UnsafeCellSlice<'a, T>, side: usize) -> Self {
+        let side = grid_side(data.len(), side, 3);
+        Self { data, side }
+    }
+
+    /// Return the side of the grid.
+    #[must_use]
+    pub fn side(&self) -> usize {
+        self.side
+    }
+
+    /// Return the underlying [`UnsafeCellSlice`] in Morton order.
+    #[must_use]
+    pub fn as_slice(&self) -> UnsafeCellSlice<'a, T> {
+        self.data
+    }
+
+    /// Return the linear offset of the cell at (`x`, `y`, `z`), or `None` if out of bounds.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn offset(&self, x: usize, y: usize, z: usize) -> Option<usize> {
+        (x < self.side && y < self.side && z < self.side)
+            .then(|| morton_encode_3d(x as u32, y as u32, z as u32) as usize)
+    }
+
+    /// Get a mutable reference to the cell at (`x`, `y`, `z`).
+    ///
+    /// Returns `None` if the cell is out of bounds.
+    ///
+    /// # Safety
+    /// This is very unsafe because it is capable of creating multiple mutable references to the same data.
+    /// It is the responsibility of the caller to only access non-overlapping subslices to avoid data races and undefined behavior.
+    #[must_use]
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn get_mut(&self, x: usize, y: usize, z: usize) -> Option<&mut T> {
+        self.data.get_mut(self.offset(x, y, z)?)
+    }
+
+    /// Get a mutable reference to the cell at (`x`, `y`, `z`).
+    ///
+    /// # Safety
+    /// This is very unsafe because it is capable of creating multiple mutable references to the same data.
+    /// It is the responsibility of the caller to only access non-overlapping subslices to avoid data races and undefined behavior.
+    ///
+    /// # Panics
+    /// Panics if the cell is out of bounds.
+    #[must_use]
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn index_mut(&self, x: usize, y: usize, z: usize) -> &mut T {
+        self.get_mut(x, y, z).expect("index out of bounds")
+    }
+
+    /// Return the contiguous runs of linear offsets covering the cells in a cuboid block, in increasing order.
+    ///
+    /// # Panics
+    /// Panics if the block is out of bounds.
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn block_runs(
+        &self,
+        x: Range<usize>,
+        y: Range<usize>,
+        z: Range<usize>,
+    ) -> Vec<Range<usize>> {
+        assert!(
+            x.end <= self.side && y.end <= self.side && z.end <= self.side,
+            "block out of bounds"
+        );
+        let mut runs = Vec::new();
+        if !x.is_empty() && !y.is_empty() && !z.is_empty() {
+            self.block_runs_impl(&[x, y, z], [0, 0, 0], self.side, 0, &mut runs);
+        }
+        runs
+    }
+
+    #[cfg(feature = "alloc")]
+    fn block_runs_impl(
+        &self,
+        block: &[Range<usize>; 3],
+        origin: [usize; 3],
+        side: usize,
+        offset: usize,
+        runs: &mut Vec<Range<usize>>,
+    ) {
+        let mut inside = true;
+        for (origin, range) in origin.iter().zip(block) {
+            let (dim_inside, dim_intersects) = overlap(*origin, side, range);
+            if !dim_intersects {
+                return;
+            }
+            inside &= dim_inside;
+        }
+        if inside {
+            push_run(runs, offset..offset + side * side * side);
+        } else {
+            let half = side / 2;
+            for child in 0..8 {
+                let origin = [
+                    origin[0] + (child & 1) * half,
+                    origin[1] + ((child >> 1) & 1) * half,
+                    origin[2] + (child >> 2) * half,
+                ];
+                self.block_runs_impl(
+                    block,
+                    origin,
+                    half,
+                    offset + child * half * half * half,
+                    runs,
+                );
+            }
+        }
+    }
+
+    /// Split the grid into its eight octants in Morton order, with the x offset varying fastest.
+    ///
+    /// Each octant is a [`MortonGrid3D`] over a disjoint contiguous eighth of the underlying slice.
+    /// Returns `None` if the grid has a side of one.
+    #[must_use]
+    pub fn octants(&self) -> Option<[Self; 8]> {
+        let half = self.side / 2;
+        if half == 0 {
+            return None;
+        }
+        let data = self.data;
+        let len = half * half * half;
+        Some(core::array::from_fn(|child| Self {
+            data: data.subslice(child * len, (child + 1) * len),
+            side: half,
+        }))
+    }
+}
+
+/// A two-dimensional view of an [`UnsafeCellSlice`] stored in Hilbert curve order. Permits acquisition of multiple mutable references of cells by (x, y) coordinates.
+///
+/// The grid is square with a power of two side.
+///
+/// This is inherently unsafe.
+/// It is the responsibility of the caller to only access non-overlapping subslices/elements to avoid data races and undefined behavior.
+pub struct HilbertGrid2D<'a, T> {
+    data: UnsafeCellSlice<'a, T>,
+    side: usize,
+}
+
+impl<T> Clone for HilbertGrid2D<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for HilbertGrid2D<'_, T> {}
+
+impl<'a, T> HilbertGrid2D<'a, T> {
+    /// Create a new [`HilbertGrid2D`] with `side * side` cells.
+    ///
+    /// # Panics
+    /// Panics if `side` is not a power of two or the length of `data` is not `side * side`.
+    #[must_use]
+    pub fn new(data: UnsafeCellSlice<'a, T>, side: usize) -> Self {
+        let side = grid_side(data.len(), side, 2);
+        Self { data, side }
+    }
+
+    /// Return the side of the grid.
+    #[must_use]
+    pub fn side(&self) -> usize {
+        self.side
+    }
+
+    /// Return the underlying [`UnsafeCellSlice`] in Hilbert curve order.
+    #[must_use]
+    pub fn as_slice(&self) -> UnsafeCellSlice<'a, T> {
+        self.data
+    }
+
+    /// Return the linear offset of the cell at (`x`, `y`), or `None` if out of bounds.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn offset(&self, x: usize, y: usize) -> Option<usize> {
+        (x < self.side && y < self.side)
+            .then(|| hilbert_encode_2d(self.side.trailing_zeros(), x as u32, y as u32) as usize)
+    }
+
+    /// Get a mutable reference to the cell at (`x`, `y`).
+    ///
+    /// Returns `None` if the cell is out of bounds.
+    ///
+    /// # Safety
+    /// This is very unsafe because it is capable of creating multiple mutable references to the same data.
+    /// It is the responsibility of the caller to only access non-overlapping subslices to avoid data races and undefined behavior.
+    #[must_use]
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn get_mut(&self, x: usize, y: usize) -> Option<&mut T> {
+        self.data.get_mut(self.offset(x, y)?)
+    }
+
+    /// Get a mutable reference to the cell at (`x`, `y`).
+    ///
+    /// # Safety
+    /// This is very unsafe because it is capable of creating multiple mutable references to the same data.
+    /// It is the responsibility of the caller to only access non-overlapping subslices to avoid data races and undefined behavior.
+    ///
+    /// # Panics
+    /// Panics if the cell is out of bounds.
+    #[must_use]
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn index_mut(&self, x: usize, y: usize) -> &mut T {
+        self.get_mut(x, y).expect("index out of bounds")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn morton_codes() {
+        assert_eq!(morton_encode_2d(0b11, 0b01), 0b0111);
+        assert_eq!(morton_encode_3d(1, 0, 1), 0b101);
+        for (x, y) in [(0, 0), (5, 9), (u32::MAX, 0), (12345, u32::MAX)] {
+            assert_eq!(morton_decode_2d(morton_encode_2d(x, y)), (x, y));
+        }
+        for (x, y, z) in [(0, 0, 0), (5, 9, 2), (0x1F_FFFF, 0, 77)] {
+            assert_eq!(morton_decode_3d(morton_encode_3d(x, y, z)), (x, y, z));
+        }
+    }
+
+    #[test]
+    fn hilbert_codes() {
+        let order = 3;
+        let side = 1 << order;
+        let mut seen = vec![false; side * side];
+        let mut prev: Option<(u32, u32)> = None;
+        for index in 0..(side * side) as u64 {
+            let (x, y) = hilbert_decode_2d(order, index);
+            assert_eq!(hilbert_encode_2d(order, x, y), index);
+            seen[index as usize] = true;
+            if let Some((px, py)) = prev {
+                assert_eq!(px.abs_diff(x) + py.abs_diff(y), 1);
+            }
+            prev = Some((x, y));
+        }
+        assert!(seen.iter().all(|s| *s));
+        assert_eq!(hilbert_decode_2d(0, 0), (0, 0));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[allow(clippy::single_range_in_vec_init)]
+    fn morton_grid_2d() {
+        let mut data = vec![(0usize, 0usize); 16];
+        {
+            let grid = MortonGrid2D::new(UnsafeCellSlice::new(&mut data), 4);
+            for y in 0..4 {
+                for x in 0..4 {
+                    *unsafe { grid.index_mut(x, y) } = (x, y);
+                }
+            }
+            assert!(unsafe { grid.get_mut(4, 0) }.is_none());
+            assert_eq!(grid.block_runs(0..4, 0..4), [0..16]);
+            assert_eq!(grid.block_runs(2..4, 2..4), [12..16]);
+            assert_eq!(grid.block_runs(1..2, 1..3), [3..4, 9..10]);
+            assert!(grid.block_runs(1..1, 0..4).is_empty());
+            let quadrants = grid.quadrants().unwrap();
+            assert_eq!(*unsafe { quadrants[3].index_mut(1, 0) }, (3, 2));
+            assert!(quadrants[0].quadrants().unwrap()[0].quadrants().is_none());
+        }
+        assert_eq!(data[0..4], [(0, 0), (1, 0), (0, 1), (1, 1)]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn morton_grid_2d_block_runs_exhaustive() {
+        use std::vec::Vec;
+
+        let mut data = vec![0u8; 64];
+        let grid = MortonGrid2D::new(UnsafeCellSlice::new(&mut data), 8);
+        for (x, y) in [(0..3, 2..7), (5..8, 0..1), (1..8, 1..8)] {
+            let runs = grid.block_runs(x.clone(), y.clone());
+            assert!(runs.windows(2).all(|runs| runs[0].end <= runs[1].start));
+            let offsets: Vec<_> = runs.into_iter().flatten().collect();
+            let mut expected: Vec<_> = y
+                .flat_map(|y| x.clone().map(move |x| (x, y)))
+                .map(|(x, y)| grid.offset(x, y).unwrap())
+                .collect();
+            expected.sort_unstable();
+            assert_eq!(offsets, expected);
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[allow(clippy::single_range_in_vec_init)]
+    fn morton_grid_3d() {
+        let mut data = vec![0u32; 64];
+        let grid = MortonGrid3D::new(UnsafeCellSlice::new(&mut data), 4);
+        assert_eq!(grid.offset(1, 1, 1), Some(7));
+        assert_eq!(grid.offset(0, 0, 2), Some(32));
+        assert!(unsafe { grid.get_mut(0, 0, 4) }.is_none());
+        assert_eq!(grid.block_runs(0..2, 0..2, 0..4), [0..8, 32..40]);
+        assert_eq!(grid.block_runs(0..4, 0..4, 0..4), [0..64]);
+        let octants = grid.octants().unwrap();
+        *unsafe { octants[7].index_mut(0, 0, 0) } = 1;
+        assert_eq!(*unsafe { grid.index_mut(2, 2, 2) }, 1);
+    }
+
+    #[test]
+    fn hilbert_grid_2d() {
+        let mut data = vec![0u8; 16];
+        let grid = HilbertGrid2D::new(UnsafeCellSlice::new(&mut data), 4);
+        assert_eq!(grid.offset(0, 0), Some(0));
+        assert_eq!(grid.offset(3, 0), Some(15));
+        assert!(unsafe { grid.get_mut(0, 4) }.is_none());
+        *unsafe { grid.index_mut(1, 0) } = 1;
+        assert_eq!(data[1], 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn morton_grid_2d_invalid_len() {
+        let mut data = vec![0u8; 15];
+        let _ = MortonGrid2D::new(UnsafeCellSlice::new(&mut data), 4);
+    }
+}
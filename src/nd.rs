@@ -0,0 +1,295 @@
+use core::ptr::NonNull;
+
+use ndarray::{ArrayViewMut, Axis, Dimension, IntoDimension, ShapeBuilder};
+
+use crate::UnsafeCellSlice;
+
+/// An N-dimensional view of an [`ndarray::ArrayViewMut`] backed by an [`UnsafeCellSlice`]. Permits acquisition of multiple mutable references of elements by N-dimensional indices.
+///
+/// The view may be contiguous or strided, including with negative strides.
+/// It can be split into disjoint views along an axis with [`split_at`](NdCellView::split_at), and converted back into an [`ndarray::ArrayViewMut`] with [`into_array_view_mut`](NdCellView::into_array_view_mut).
+///
+/// This is inherently unsafe.
+/// It is the responsibility of the caller to only access non-overlapping elements to avoid data races and undefined behavior.
+///
+/// ```rust
+/// # use ndarray::{s, Array2, Axis};
+/// # use unsafe_cell_slice::NdCellView;
+/// let mut array = Array2::<u32>::zeros((4, 6));
+/// let view = NdCellView::new(array.slice_mut(s![.., ..;2]));
+/// std::thread::scope(|s| {
+///     let (top, bottom) = view.split_at(Axis(0), 2);
+///     s.spawn(move || unsafe { top.into_array_view_mut() }.fill(1));
+///     s.spawn(move || *unsafe { bottom.index_mut((1, 2)) } = 2);
+/// });
+/// assert_eq!(array.row(0).to_vec(), [1, 0, 1, 0, 1, 0]);
+/// assert_eq!(array[(3, 4)], 2);
+/// ```
+pub struct NdCellView<'a, T, D: Dimension> {
+    data: UnsafeCellSlice<'a, T>,
+    origin: usize,
+    dim: D,
+    strides: D,
+}
+
+impl<T, D: Dimension> Clone for NdCellView<'_, T, D> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data,
+            origin: self.origin,
+            dim: self.dim.clone(),
+            strides: self.strides.clone(),
+        }
+    }
+}
+
+unsafe impl<T: Send, D: Dimension> Send for NdCellView<'_, T, D> {}
+unsafe impl<T: Send, D: Dimension> Sync for NdCellView<'_, T, D> {}
+
+impl<'a, T, D: Dimension> NdCellView<'a, T, D> {
+    /// Create a new [`NdCellView`] from a mutable array view.
+    #[must_use]
+    pub fn new(mut view: ArrayViewMut<'a, T, D>) -> Self {
+        let dim = view.raw_dim();
+        let mut strides = dim.clone();
+        for (stride, &view_stride) in strides.slice_mut().iter_mut().zip(view.strides()) {
+            *stride = view_stride as usize;
+        }
+        let ptr = view.as_mut_ptr();
+        if dim.slice().contains(&0) {
+            // SAFETY: the view is empty, so no elements are accessible
+            let data = unsafe { UnsafeCellSlice::from_raw_parts(NonNull::dangling(), 0) };
+            return Self {
+                data,
+                origin: 0,
+                dim,
+                strides,
+            };
+        }
+        let (mut min, mut max) = (0isize, 0isize);
+        for (&len, &stride) in dim.slice().iter().zip(view.strides()) {
+            let extent = (len - 1) as isize * stride;
+            if extent < 0 {
+                min += extent;
+            } else {
+                max += extent;
+            }
+        }
+        // SAFETY: the elements from `min` to `max` relative to `ptr` are in one allocation, so the span is valid to offset within.
+        // It may include elements that the view does not own (e.g. the other half of `multi_slice_mut`), but those are only spanned, never accessed,
+        // since `offset` only yields the elements of the view, which is exclusively borrowed for 'a
+        let data = unsafe {
+            UnsafeCellSlice::from_raw_parts(
+                NonNull::new_unchecked(ptr.offset(min)),
+                (max - min) as usize + 1,
+            )
+        };
+        Self {
+            data,
+            origin: min.unsigned_abs(),
+            dim,
+            strides,
+        }
+    }
+
+    /// Return the shape of the view.
+    #[must_use]
+    pub fn shape(&self) -> &[usize] {
+        self.dim.slice()
+    }
+
+    /// Return the shape of the view as a [`Dimension`].
+    #[must_use]
+    pub fn raw_dim(&self) -> D {
+        self.dim.clone()
+    }
+
+    /// Return the number of dimensions of the view.
+    #[must_use]
+    pub fn ndim(&self) -> usize {
+        self.dim.ndim()
+    }
+
+    /// Return the number of elements in the view.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.dim.size()
+    }
+
+    /// Return whether the view is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the offset of the element at `index` in the underlying [`UnsafeCellSlice`], or `None` if out of bounds.
+    fn offset(&self, index: D) -> Option<usize> {
+        let mut offset = self.origin as isize;
+        for ((&i, &len), &stride) in index
+            .slice()
+            .iter()
+            .zip(self.dim.slice())
+            .zip(self.strides.slice())
+        {
+            if i >= len {
+                return None;
+            }
+            offset += i as isize * stride as isize;
+        }
+        Some(offset as usize)
+    }
+
+    /// Get a mutable reference to the element at `index`.
+    ///
+    /// Returns `None` if the index is out of bounds.
+    ///
+    /// # Safety
+    /// This is very unsafe because it is capable of creating multiple mutable references to the same data.
+    /// It is the responsibility of the caller to only access non-overlapping elements to avoid data races and undefined behavior.
+    #[must_use]
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn get_mut<I: IntoDimension<Dim = D>>(&self, index: I) -> Option<&mut T> {
+        let offset = self.offset(index.into_dimension())?;
+        self.data.get_mut(offset)
+    }
+
+    /// Get a mutable reference to the element at `index`.
+    ///
+    /// # Safety
+    /// This is very unsafe because it is capable of creating multiple mutable references to the same data.
+    /// It is the responsibility of the caller to only access non-overlapping elements to avoid data races and undefined behavior.
+    ///
+    /// # Panics
+    /// Panics if the index is out of bounds.
+    #[must_use]
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn index_mut<I: IntoDimension<Dim = D>>(&self, index: I) -> &mut T {
+        self.get_mut(index).expect("index out of bounds")
+    }
+
+    /// Split the view into two disjoint views along `axis`, before and after `index`.
+    ///
+    /// # Panics
+    /// Panics if `axis` or `index` is out of bounds.
+    #[must_use]
+    pub fn split_at(self, axis: Axis, index: usize) -> (Self, Self) {
+        let len = self.dim[axis.index()];
+        assert!(index <= len, "split index out of bounds");
+        let mut left = self.clone();
+        left.dim[axis.index()] = index;
+        let mut right = self;
+        right.dim[axis.index()] = len - index;
+        if len - index != 0 {
+            let stride = right.strides[axis.index()] as isize;
+            right.origin = (right.origin as isize + index as isize * stride) as usize;
+        }
+        (left, right)
+    }
+
+    /// Convert the view into a mutable array view.
+    ///
+    /// # Safety
+    /// The returned view permits safe mutable access to all elements of this view.
+    /// It is the responsibility of the caller to not access these elements through any other reference while the returned view exists.
+    #[must_use]
+    pub unsafe fn into_array_view_mut(self) -> ArrayViewMut<'a, T, D> {
+        // ndarray requires non-negative strides, so negative axes are inverted after construction
+        let mut ptr = self.data.as_mut_ptr().add(self.origin);
+        let mut strides = self.strides.clone();
+        for ((stride, &len), &view_stride) in strides
+            .slice_mut()
+            .iter_mut()
+            .zip(self.dim.slice())
+            .zip(self.strides.slice())
+        {
+            let view_stride = view_stride as isize;
+            if view_stride < 0 {
+                if len != 0 {
+                    ptr = ptr.offset((len - 1) as isize * view_stride);
+                }
+                *stride = view_stride.unsigned_abs();
+            }
+        }
+        let mut view = ArrayViewMut::from_shape_ptr(self.dim.clone().strides(strides), ptr);
+        for (axis, &stride) in self.strides.slice().iter().enumerate() {
+            if (stride as isize) < 0 {
+                view.invert_axis(Axis(axis));
+            }
+        }
+        view
+    }
+}
+
+impl<'a, T, D: Dimension> From<ArrayViewMut<'a, T, D>> for NdCellView<'a, T, D> {
+    fn from(view: ArrayViewMut<'a, T, D>) -> Self {
+        Self::new(view)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{s, Array2, Array3};
+
+    use super::*;
+
+    #[test]
+    fn nd_cell_view() {
+        let mut array = Array3::<u32>::zeros((2, 3, 4));
+        let view = NdCellView::new(array.view_mut());
+        assert_eq!(view.shape(), [2, 3, 4]);
+        assert_eq!((view.ndim(), view.len()), (3, 24));
+        unsafe {
+            *view.index_mut((1, 2, 3)) = 1;
+            *view.index_mut([0, 1, 0]) = 2;
+            assert!(view.get_mut((2, 0, 0)).is_none());
+            assert!(view.get_mut((0, 0, 4)).is_none());
+        }
+        assert_eq!(array[(1, 2, 3)], 1);
+        assert_eq!(array[(0, 1, 0)], 2);
+    }
+
+    #[test]
+    fn nd_cell_view_strided() {
+        let mut array = Array2::<i32>::from_shape_fn((4, 5), |(i, j)| (i * 5 + j) as i32);
+        let view = NdCellView::new(array.slice_mut(s![..;-1, 1..;2]));
+        assert_eq!(view.shape(), [4, 2]);
+        assert_eq!(*unsafe { view.index_mut((0, 0)) }, 16);
+        assert_eq!(*unsafe { view.index_mut((3, 1)) }, 3);
+        let (top, bottom) = view.split_at(Axis(0), 1);
+        assert_eq!(bottom.shape(), [3, 2]);
+        assert_eq!(*unsafe { bottom.index_mut((0, 1)) }, 13);
+        let top = unsafe { top.into_array_view_mut() };
+        assert_eq!(top.iter().copied().collect::<Vec<_>>(), [16, 18]);
+        let mut bottom = unsafe { bottom.into_array_view_mut() };
+        assert_eq!(bottom.column(0).to_vec(), [11, 6, 1]);
+        bottom.fill(0);
+        assert_eq!(array.column(1).to_vec(), [0, 0, 0, 16]);
+    }
+
+    #[test]
+    fn nd_cell_view_empty() {
+        let mut array = Array2::<u8>::zeros((3, 0));
+        let view = NdCellView::new(array.view_mut());
+        assert!(view.is_empty());
+        assert!(unsafe { view.get_mut((0, 0)) }.is_none());
+        let (left, right) = view.split_at(Axis(0), 3);
+        assert_eq!(
+            (left.shape(), right.shape()),
+            ([3, 0].as_slice(), [0, 0].as_slice())
+        );
+        assert!(unsafe { right.into_array_view_mut() }.is_empty());
+    }
+
+    #[test]
+    fn nd_cell_view_par() {
+        use rayon::prelude::*;
+        let mut array = Array2::<usize>::zeros((64, 8));
+        let view = NdCellView::new(array.view_mut());
+        (0..64).into_par_iter().for_each(|i| {
+            for j in 0..8 {
+                *unsafe { view.index_mut((i, j)) } = i * j;
+            }
+        });
+        assert!(array.indexed_iter().all(|((i, j), &v)| v == i * j));
+    }
+}
@@ -0,0 +1,204 @@
+use alloc::vec::Vec;
+
+use crate::{SliceIndex, UnsafeCellSlice};
+
+/// An unsafe cell view of nested slices. Permits acquisition of multiple mutable references of elements in a slice of slices.
+///
+/// Elements can be indexed by (bucket, index) pairs or by a flattened index over all buckets in order.
+///
+/// This is inherently unsafe.
+/// It is the responsibility of the caller to only access non-overlapping subslices/elements to avoid data races and undefined behavior.
+pub struct UnsafeCellNested<'a, T> {
+    buckets: Vec<UnsafeCellSlice<'a, T>>,
+    offsets: Vec<usize>,
+}
+
+impl<'a, T> UnsafeCellNested<'a, T> {
+    /// Create a new [`UnsafeCellNested`] from a mutable slice of buckets, such as a `&mut [Vec<T>]` or `&mut [&mut [T]]`.
+    #[must_use]
+    pub fn new<S: AsMut<[T]>>(buckets: &'a mut [S]) -> Self {
+        let buckets: Vec<_> = buckets
+            .iter_mut()
+            .map(|bucket| UnsafeCellSlice::new(bucket.as_mut()))
+            .collect();
+        let mut offsets = Vec::with_capacity(buckets.len() + 1);
+        let mut offset = 0;
+        offsets.push(offset);
+        for bucket in &buckets {
+            offset += bucket.len();
+            offsets.push(offset);
+        }
+        Self { buckets, offsets }
+    }
+
+    /// Return the total number of elements in all buckets.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.offsets[self.offsets.len() - 1]
+    }
+
+    /// Return whether there are no elements in any bucket.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the number of buckets.
+    #[must_use]
+    pub fn num_buckets(&self) -> usize {
+        self.buckets.len()
+    }
+
+    /// Return the flattened offsets of the buckets.
+    ///
+    /// Bucket `i` spans flattened indices `offsets[i]..offsets[i + 1]`.
+    #[must_use]
+    pub fn offsets(&self) -> &[usize] {
+        &self.offsets
+    }
+
+    /// Return the [`UnsafeCellSlice`] of bucket `index`, or `None` if out of bounds.
+    #[must_use]
+    pub fn bucket(&self, index: usize) -> Option<UnsafeCellSlice<'a, T>> {
+        self.buckets.get(index).copied()
+    }
+
+    /// Return an iterator over the [`UnsafeCellSlice`] of each bucket.
+    pub fn buckets(&self) -> impl ExactSizeIterator<Item = UnsafeCellSlice<'a, T>> + '_ {
+        self.buckets.iter().copied()
+    }
+
+    /// Convert a flattened index to a (bucket, index) pair, or `None` if out of bounds.
+    #[must_use]
+    pub fn unflatten_index(&self, index: usize) -> Option<(usize, usize)> {
+        if index >= self.len() {
+            return None;
+        }
+        let bucket = self.offsets.partition_point(|offset| *offset <= index) - 1;
+        Some((bucket, index - self.offsets[bucket]))
+    }
+
+    /// Get a mutable reference to a subslice or element of a bucket.
+    ///
+    /// Returns `None` if the bucket or index is out of bounds.
+    ///
+    /// # Safety
+    /// This is very unsafe because it is capable of creating multiple mutable references to the same data.
+    /// It is the responsibility of the caller to only access non-overlapping subslices to avoid data races and undefined behavior.
+    #[must_use]
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn get_mut<I>(&self, (bucket, index): (usize, I)) -> Option<&mut I::Output>
+    where
+        I: SliceIndex<T>,
+    {
+        self.buckets.get(bucket)?.get_mut(index)
+    }
+
+    /// Get a mutable reference to a subslice or element of a bucket.
+    ///
+    /// ```rust
+    /// # use unsafe_cell_slice::UnsafeCellNested;
+    /// let mut data = vec![vec![0u8; 2], vec![], vec![0u8; 3]];
+    /// {
+    ///     let data = UnsafeCellNested::new(&mut data);
+    ///     *unsafe { data.index_mut((0, 1)) } = 1;
+    ///     unsafe { data.index_mut((2, 1..)) }.fill(2);
+    ///     *unsafe { data.index_flat_mut(2) } = 3;
+    /// }
+    /// assert_eq!(data, vec![vec![0, 1], vec![], vec![3, 2, 2]]);
+    /// ```
+    ///
+    /// # Safety
+    /// This is very unsafe because it is capable of creating multiple mutable references to the same data.
+    /// It is the responsibility of the caller to only access non-overlapping subslices to avoid data races and undefined behavior.
+    ///
+    /// # Panics
+    /// May panic if the bucket or index is out of bounds.
+    #[must_use]
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn index_mut<I>(&self, (bucket, index): (usize, I)) -> &mut I::Output
+    where
+        I: SliceIndex<T>,
+    {
+        self.buckets[bucket].index_mut(index)
+    }
+
+    /// Get a mutable reference to an element by its flattened index.
+    ///
+    /// Returns `None` if the index is out of bounds.
+    ///
+    /// # Safety
+    /// This is very unsafe because it is capable of creating multiple mutable references to the same data.
+    /// It is the responsibility of the caller to only access non-overlapping subslices to avoid data races and undefined behavior.
+    #[must_use]
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn get_flat_mut(&self, index: usize) -> Option<&mut T> {
+        self.get_mut(self.unflatten_index(index)?)
+    }
+
+    /// Get a mutable reference to an element by its flattened index.
+    ///
+    /// # Safety
+    /// This is very unsafe because it is capable of creating multiple mutable references to the same data.
+    /// It is the responsibility of the caller to only access non-overlapping subslices to avoid data races and undefined behavior.
+    ///
+    /// # Panics
+    /// Panics if the index is out of bounds.
+    #[must_use]
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn index_flat_mut(&self, index: usize) -> &mut T {
+        self.get_flat_mut(index).expect("index out of bounds")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nested_vecs() {
+        let mut data = vec![vec![0i64, 1], vec![], vec![2], vec![3, 4, 5]];
+        let nested = UnsafeCellNested::new(&mut data);
+        assert_eq!(nested.len(), 6);
+        assert_eq!(nested.num_buckets(), 4);
+        assert_eq!(nested.offsets(), [0, 2, 2, 3, 6]);
+        assert_eq!(
+            nested
+                .buckets()
+                .map(|bucket| bucket.len())
+                .collect::<Vec<_>>(),
+            [2, 0, 1, 3]
+        );
+        assert_eq!(nested.unflatten_index(0), Some((0, 0)));
+        assert_eq!(nested.unflatten_index(2), Some((2, 0)));
+        assert_eq!(nested.unflatten_index(5), Some((3, 2)));
+        assert_eq!(nested.unflatten_index(6), None);
+        for i in 0..6 {
+            assert_eq!(*unsafe { nested.index_flat_mut(i) }, i as i64);
+        }
+        assert!(unsafe { nested.get_flat_mut(6) }.is_none());
+        assert_eq!(unsafe { nested.index_mut((3, 1..)) }, [4, 5]);
+        assert!(unsafe { nested.get_mut((1, 0)) }.is_none());
+        assert!(unsafe { nested.get_mut((4, 0)) }.is_none());
+        assert!(nested.bucket(4).is_none());
+    }
+
+    #[test]
+    fn nested_slices() {
+        let mut a = [0u8; 2];
+        let mut b = [0u8; 1];
+        let mut data = [a.as_mut_slice(), b.as_mut_slice()];
+        {
+            let nested = UnsafeCellNested::new(&mut data);
+            *unsafe { nested.index_mut((1, 0)) } = 1;
+            *unsafe { nested.index_flat_mut(1) } = 2;
+        }
+        assert_eq!(a, [0, 2]);
+        assert_eq!(b, [1]);
+
+        let mut empty: Vec<Vec<u8>> = vec![];
+        let nested = UnsafeCellNested::new(&mut empty);
+        assert!(nested.is_empty());
+        assert!(nested.unflatten_index(0).is_none());
+    }
+}
@@ -0,0 +1,174 @@
+use crate::UnsafeCellSlice;
+
+/// A plain old data type that can be reinterpreted as bytes and vice versa.
+///
+/// This is implemented for the primitive numeric types and arrays of [`Pod`] types.
+/// With the `bytemuck` feature, slices of any [`bytemuck::Pod`](https://docs.rs/bytemuck/latest/bytemuck/trait.Pod.html) type can also be reinterpreted with `UnsafeCellSlice::cast_bytemuck`.
+///
+/// # Safety
+/// Implementors must be inhabited, have no padding bytes, accept any bit pattern, and contain no pointers or interior mutability.
+pub unsafe trait Pod: Copy + 'static {}
+
+macro_rules! impl_pod {
+    ($($t:ty),*) => {
+        $(unsafe impl Pod for $t {})*
+    };
+}
+
+impl_pod!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+
+/// An error casting an [`UnsafeCellSlice`] to another element type.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CastError {
+    /// The start of the slice is not sufficiently aligned for the target element type.
+    Alignment,
+    /// The size of the slice in bytes is not a multiple of the size of the target element type.
+    Size,
+}
+
+impl core::fmt::Display for CastError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Alignment => write!(f, "slice is not aligned for the target element type"),
+            Self::Size => write!(
+                f,
+                "slice size is not a multiple of the target element type size"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CastError {}
+
+impl<'a, T: Pod> UnsafeCellSlice<'a, T> {
+    /// Reinterpret the underlying slice as bytes.
+    #[must_use]
+    pub fn as_bytes(&self) -> UnsafeCellSlice<'a, u8> {
+        let len = core::mem::size_of::<T>() * self.len();
+        // SAFETY: `T` is plain old data with no padding
+        unsafe { self.cast_unchecked::<u8>(len) }
+    }
+
+    /// Reinterpret the underlying slice as a slice of another [`Pod`] element type.
+    ///
+    /// ```rust
+    /// # use unsafe_cell_slice::UnsafeCellSlice;
+    /// let mut data = vec![0u32; 2];
+    /// let data = UnsafeCellSlice::new(&mut data);
+    /// let bytes = data.cast::<u8>().unwrap();
+    /// assert_eq!(bytes.len(), 8);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns a [`CastError`] if the start of the slice is not aligned for `U` or the size of the slice in bytes is not a multiple of the size of `U`.
+    pub fn cast<U: Pod>(&self) -> Result<UnsafeCellSlice<'a, U>, CastError> {
+        // SAFETY: `T` and `U` are plain old data
+        unsafe { self.cast_pod() }
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+impl<'a, T: bytemuck::Pod> UnsafeCellSlice<'a, T> {
+    /// Reinterpret the underlying slice as a slice of another [`bytemuck::Pod`](https://docs.rs/bytemuck/latest/bytemuck/trait.Pod.html) element type.
+    ///
+    /// # Errors
+    /// Returns a [`CastError`] if the start of the slice is not aligned for `U` or the size of the slice in bytes is not a multiple of the size of `U`.
+    pub fn cast_bytemuck<U: bytemuck::Pod>(&self) -> Result<UnsafeCellSlice<'a, U>, CastError> {
+        // SAFETY: `T` and `U` are plain old data
+        unsafe { self.cast_pod() }
+    }
+}
+
+impl<'a, T> UnsafeCellSlice<'a, T> {
+    /// Reinterpret the underlying slice as a slice of `U`, checking the alignment and size.
+    ///
+    /// # Safety
+    /// `T` and `U` must be plain old data.
+    unsafe fn cast_pod<U>(&self) -> Result<UnsafeCellSlice<'a, U>, CastError> {
+        let size = core::mem::size_of::<T>() * self.len();
+        let size_u = core::mem::size_of::<U>();
+        let len = if size_u == 0 {
+            if size == 0 && core::mem::size_of::<T>() == 0 {
+                self.len()
+            } else {
+                return Err(CastError::Size);
+            }
+        } else if size == 0 {
+            return Ok(UnsafeCellSlice::new(&mut []));
+        } else if size % size_u != 0 {
+            return Err(CastError::Size);
+        } else {
+            size / size_u
+        };
+        if self.as_mut_ptr() as usize % core::mem::align_of::<U>() != 0 {
+            return Err(CastError::Alignment);
+        }
+        // SAFETY: the slice is aligned for `U` and spans `len` elements of `U`, and both are plain old data
+        Ok(self.cast_unchecked::<U>(len))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn as_bytes() {
+        let mut data = vec![0u16, 1];
+        {
+            let data = UnsafeCellSlice::new(&mut data);
+            let bytes = data.as_bytes();
+            assert_eq!(bytes.len(), 4);
+            unsafe { bytes.index_mut(2..) }.copy_from_slice(&2u16.to_ne_bytes());
+        }
+        assert_eq!(data, [0, 2]);
+    }
+
+    #[test]
+    fn cast() {
+        let mut data = vec![0u32; 3];
+        {
+            let data = UnsafeCellSlice::new(&mut data);
+            let halves = data.cast::<u16>().unwrap();
+            assert_eq!(halves.len(), 6);
+            *unsafe { halves.index_mut(2) } = u16::MAX;
+            *unsafe { halves.index_mut(3) } = u16::MAX;
+            let bytes = data.as_bytes();
+            assert_eq!(bytes.cast::<u32>().unwrap().len(), 3);
+            assert_eq!(bytes.cast::<[u8; 4]>().unwrap().len(), 3);
+            assert_eq!(data.cast::<u64>().err(), Some(CastError::Size));
+            let unaligned = UnsafeCellSlice::new(unsafe { bytes.index_mut(1..5) });
+            assert_eq!(unaligned.cast::<u32>().err(), Some(CastError::Alignment));
+            let empty = UnsafeCellSlice::new(unsafe { bytes.index_mut(1..1) });
+            assert!(empty.cast::<u64>().unwrap().is_empty());
+        }
+        assert_eq!(data, [0, u32::MAX, 0]);
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn cast_bytemuck() {
+        #[derive(Copy, Clone)]
+        #[repr(C)]
+        struct Rgba([u8; 4]);
+        unsafe impl bytemuck::Zeroable for Rgba {}
+        unsafe impl bytemuck::Pod for Rgba {}
+        unsafe impl Pod for Rgba {}
+
+        let mut data = vec![0u32; 2];
+        {
+            let data = UnsafeCellSlice::new(&mut data);
+            let pixels = data.cast_bytemuck::<Rgba>().unwrap();
+            assert_eq!(pixels.len(), 2);
+            unsafe { pixels.index_mut(1) }.0 = [0xff; 4];
+            assert_eq!(data.cast_bytemuck::<u64>().unwrap().len(), 1);
+            assert_eq!(data.cast_bytemuck::<[u8; 3]>().err(), Some(CastError::Size));
+            assert_eq!(data.cast::<Rgba>().unwrap().len(), 2);
+            assert_eq!(data.cast::<[u8; 40]>().err(), Some(CastError::Size));
+        }
+        assert_eq!(data, [0, u32::MAX]);
+    }
+}
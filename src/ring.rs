@@ -0,0 +1,295 @@
+#[cfg(feature = "alloc")]
+use alloc::collections::VecDeque;
+use core::ops::{Bound, RangeBounds};
+
+use crate::UnsafeCellSlice;
+
+/// An unsafe cell ring buffer. Permits acquisition of multiple mutable references of a ring buffer in logical order.
+///
+/// A ring buffer is stored as up to two segments, so subslices of an [`UnsafeCellRing`] are returned as a pair of slices.
+/// The second slice is non-empty only if the subslice wraps around the end of the underlying buffer.
+///
+/// This is inherently unsafe.
+/// It is the responsibility of the caller to only access non-overlapping subslices/elements to avoid data races and undefined behavior.
+pub struct UnsafeCellRing<'a, T> {
+    front: UnsafeCellSlice<'a, T>,
+    back: UnsafeCellSlice<'a, T>,
+}
+
+impl<T> Clone for UnsafeCellRing<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for UnsafeCellRing<'_, T> {}
+
+impl<'a, T> UnsafeCellRing<'a, T> {
+    /// Create a new [`UnsafeCellRing`] from a mutable [`VecDeque`].
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn new(deque: &'a mut VecDeque<T>) -> Self {
+        let (front, back) = deque.as_mut_slices();
+        Self::from_slices(front, back)
+    }
+
+    /// Create a new [`UnsafeCellRing`] from the front and back segments of a ring buffer.
+    #[must_use]
+    pub fn from_slices(front: &'a mut [T], back: &'a mut [T]) -> Self {
+        Self {
+            front: UnsafeCellSlice::new(front),
+            back: UnsafeCellSlice::new(back),
+        }
+    }
+
+    /// Create a new [`UnsafeCellRing`] from a ring `buffer` with `len` elements starting at `head`.
+    ///
+    /// # Panics
+    /// Panics if `head` is out of bounds of a non-empty `buffer` or `len` exceeds the length of `buffer`.
+    #[must_use]
+    pub fn from_raw_ring(buffer: &'a mut [T], head: usize, len: usize) -> Self {
+        assert!(
+            head < buffer.len() || head == 0,
+            "ring head is out of bounds"
+        );
+        assert!(len <= buffer.len(), "ring length exceeds the buffer length");
+        let (back, front) = buffer.split_at_mut(head);
+        let front_len = len.min(front.len());
+        Self::from_slices(&mut front[..front_len], &mut back[..len - front_len])
+    }
+
+    /// Return the number of elements in the ring.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+
+    /// Return whether the ring is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the front and back segments of the ring.
+    #[must_use]
+    pub fn as_slices(&self) -> (UnsafeCellSlice<'a, T>, UnsafeCellSlice<'a, T>) {
+        (self.front, self.back)
+    }
+
+    /// Get a mutable reference to an element or subslice of the ring in logical order.
+    ///
+    /// Returns `None` if the index is out of bounds.
+    ///
+    /// # Safety
+    /// This is very unsafe because it is capable of creating multiple mutable references to the same data.
+    /// It is the responsibility of the caller to only access non-overlapping subslices to avoid data races and undefined behavior.
+    #[must_use]
+    pub unsafe fn get_mut<'b, I>(&'b self, index: I) -> Option<I::Output>
+    where
+        I: RingIndex<'b, T>,
+    {
+        index.get_mut(self)
+    }
+
+    /// Get a mutable reference to an element or subslice of the ring in logical order.
+    ///
+    /// ```rust
+    /// # use std::collections::VecDeque;
+    /// # use unsafe_cell_slice::UnsafeCellRing;
+    /// let mut deque = VecDeque::with_capacity(4);
+    /// deque.extend([0, 0, 1, 2]);
+    /// deque.pop_front();
+    /// deque.pop_front();
+    /// deque.extend([3, 4]);
+    /// let ring = UnsafeCellRing::new(&mut deque);
+    /// let (front, back): (&mut [i32], &mut [i32]) = unsafe { ring.index_mut(..) };
+    /// assert_eq!(front.len() + back.len(), 4);
+    /// assert_eq!(*unsafe { ring.index_mut(3) }, 4);
+    /// ```
+    ///
+    /// # Safety
+    /// This is very unsafe because it is capable of creating multiple mutable references to the same data.
+    /// It is the responsibility of the caller to only access non-overlapping subslices to avoid data races and undefined behavior.
+    ///
+    /// # Panics
+    /// May panic if the index is out of bounds.
+    #[must_use]
+    pub unsafe fn index_mut<'b, I>(&'b self, index: I) -> I::Output
+    where
+        I: RingIndex<'b, T>,
+    {
+        index.index_mut(self)
+    }
+}
+
+mod private_ring_index {
+    pub trait Sealed {}
+}
+
+/// A trait for indexing into an [`UnsafeCellRing`].
+///
+/// Elements are indexed as `&mut T` and subslices are indexed as a pair of `&mut [T]`.
+///
+/// # Safety
+/// Callers of the trait methods must ensure that overlapping subslices/elements are not requested.
+pub unsafe trait RingIndex<'a, T: 'a>: private_ring_index::Sealed {
+    /// The output reference type(s).
+    type Output;
+
+    /// Returns a mutable reference to the output at this location, if in bounds.
+    ///
+    /// # Safety
+    /// The caller must ensure that overlapping subslices/elements are not requested.
+    unsafe fn get_mut(self, ring: &'a UnsafeCellRing<'_, T>) -> Option<Self::Output>;
+
+    /// Returns a mutable reference to the output at this location, panicking
+    /// if out of bounds.
+    ///
+    /// # Safety
+    /// The caller must ensure that overlapping subslices/elements are not requested.
+    unsafe fn index_mut(self, ring: &'a UnsafeCellRing<'_, T>) -> Self::Output;
+}
+
+impl private_ring_index::Sealed for usize {}
+
+unsafe impl<'a, T: 'a> RingIndex<'a, T> for usize {
+    type Output = &'a mut T;
+
+    unsafe fn get_mut(self, ring: &'a UnsafeCellRing<'_, T>) -> Option<Self::Output> {
+        match self.checked_sub(ring.front.len()) {
+            None => ring.front.get_mut(self),
+            Some(index) => ring.back.get_mut(index),
+        }
+    }
+
+    unsafe fn index_mut(self, ring: &'a UnsafeCellRing<'_, T>) -> Self::Output {
+        self.get_mut(ring).expect("index out of bounds")
+    }
+}
+
+/// Convert range bounds to a range in a ring of length `len`, if in bounds.
+fn bounds_to_range<R: RangeBounds<usize>>(
+    range: &R,
+    len: usize,
+) -> Option<core::ops::Range<usize>> {
+    let start = match range.start_bound() {
+        Bound::Included(start) => *start,
+        Bound::Excluded(start) => start.checked_add(1)?,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(end) => end.checked_add(1)?,
+        Bound::Excluded(end) => *end,
+        Bound::Unbounded => len,
+    };
+    (start <= end && end <= len).then_some(start..end)
+}
+
+macro_rules! impl_ring_index_range {
+    ($($range:ty),*) => {
+        $(
+            impl private_ring_index::Sealed for $range {}
+
+            unsafe impl<'a, T: 'a> RingIndex<'a, T> for $range {
+                type Output = (&'a mut [T], &'a mut [T]);
+
+                unsafe fn get_mut(self, ring: &'a UnsafeCellRing<'_, T>) -> Option<Self::Output> {
+                    let range = bounds_to_range(&self, ring.len())?;
+                    let front_len = ring.front.len();
+                    let front = range.start.min(front_len)..range.end.min(front_len);
+                    let back = range.start.saturating_sub(front_len)..range.end.saturating_sub(front_len);
+                    Some((ring.front.get_mut(front)?, ring.back.get_mut(back)?))
+                }
+
+                unsafe fn index_mut(self, ring: &'a UnsafeCellRing<'_, T>) -> Self::Output {
+                    self.get_mut(ring).expect("index out of bounds")
+                }
+            }
+        )*
+    };
+}
+
+impl_ring_index_range!(
+    core::ops::Range<usize>,
+    core::ops::RangeFrom<usize>,
+    core::ops::RangeFull,
+    core::ops::RangeInclusive<usize>,
+    core::ops::RangeTo<usize>,
+    core::ops::RangeToInclusive<usize>
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::vec::Vec;
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn ring_deque() {
+        let mut deque = VecDeque::with_capacity(4);
+        deque.extend([0i64, 0, 1, 2]);
+        deque.pop_front();
+        deque.pop_front();
+        deque.extend([3, 4]);
+        {
+            let ring = UnsafeCellRing::new(&mut deque);
+            assert_eq!(ring.len(), 4);
+            for i in 0..4 {
+                *unsafe { ring.index_mut(i) } *= 10;
+            }
+            assert!(unsafe { ring.get_mut(4) }.is_none());
+        }
+        assert_eq!(deque, [10, 20, 30, 40]);
+    }
+
+    #[test]
+    fn ring_raw() {
+        let mut buffer = vec![3i64, 4, 5, 0, 1, 2];
+        let ring = UnsafeCellRing::from_raw_ring(&mut buffer, 4, 5);
+        assert_eq!(ring.len(), 5);
+        assert_eq!(ring.as_slices().0.len(), 2);
+        assert_eq!(ring.as_slices().1.len(), 3);
+        assert_eq!(*unsafe { ring.index_mut(0) }, 1);
+        assert_eq!(*unsafe { ring.index_mut(2) }, 3);
+        assert_eq!(*unsafe { ring.index_mut(4) }, 5);
+        assert!(unsafe { ring.get_mut(5) }.is_none());
+        {
+            let (a, b) = unsafe { ring.index_mut(1..4) };
+            assert_eq!(a, [2]);
+            assert_eq!(b, [3, 4]);
+        }
+        {
+            let (a, b) = unsafe { ring.index_mut(..=1) };
+            assert_eq!(a, [1, 2]);
+            assert!(b.is_empty());
+        }
+        {
+            let (a, b) = unsafe { ring.index_mut(3..) };
+            assert!(a.is_empty());
+            assert_eq!(b, [4, 5]);
+        }
+        {
+            let (a, b) = unsafe { ring.index_mut(..) };
+            assert_eq!(a.len() + b.len(), 5);
+        }
+        assert!(unsafe { ring.get_mut(..6) }.is_none());
+        assert!(unsafe { ring.get_mut(3..=5) }.is_none());
+        assert!(unsafe { ring.get_mut(..=usize::MAX) }.is_none());
+
+        let ring = UnsafeCellRing::from_raw_ring(&mut buffer, 1, 2);
+        assert_eq!(
+            unsafe { ring.index_mut(..) },
+            ([4, 5].as_mut_slice(), [].as_mut_slice())
+        );
+
+        let mut empty: Vec<u8> = vec![];
+        assert!(UnsafeCellRing::from_raw_ring(&mut empty, 0, 0).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn ring_raw_invalid() {
+        let mut buffer = vec![0u8; 2];
+        let _ = UnsafeCellRing::from_raw_ring(&mut buffer, 2, 0);
+    }
+}
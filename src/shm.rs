@@ -0,0 +1,391 @@
+use std::{
+    fs::File,
+    marker::PhantomData,
+    os::unix::io::{AsFd, AsRawFd, BorrowedFd, FromRawFd, OwnedFd, RawFd},
+    ptr::NonNull,
+};
+
+use crate::{Pod, UnsafeCellSlice};
+
+/// The magic bytes at the start of a [`SharedCellBuffer`] header.
+const MAGIC: [u8; 8] = *b"UCSSHM01";
+
+/// The length of a [`SharedCellBuffer`] header in bytes, which is also the maximum element alignment.
+const HEADER_LEN: usize = 64;
+
+/// The header at the start of a [`SharedCellBuffer`] mapping.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct Header {
+    magic: [u8; 8],
+    elem_size: u64,
+    elem_align: u64,
+    len: u64,
+}
+
+/// An error creating or attaching to a [`SharedCellBuffer`].
+#[derive(Debug)]
+pub enum SharedCellBufferError {
+    /// An I/O error.
+    Io(std::io::Error),
+    /// The alignment of the element type exceeds the header length of 64 bytes.
+    Alignment(usize),
+    /// The size of the buffer in bytes overflows a [`usize`].
+    Overflow,
+    /// The file does not start with a [`SharedCellBuffer`] header.
+    Header,
+    /// The element size recorded in the header does not match the element type.
+    ElementSize {
+        /// The size of the element type.
+        expected: usize,
+        /// The element size recorded in the header.
+        found: u64,
+    },
+    /// The element alignment recorded in the header does not match the element type.
+    ElementAlignment {
+        /// The alignment of the element type.
+        expected: usize,
+        /// The element alignment recorded in the header.
+        found: u64,
+    },
+    /// The file is too short for the length recorded in the header.
+    Length {
+        /// The length recorded in the header.
+        len: u64,
+        /// The size of the file in bytes.
+        size: u64,
+    },
+}
+
+impl core::fmt::Display for SharedCellBufferError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "{err}"),
+            Self::Alignment(align) => write!(
+                f,
+                "element alignment {align} exceeds the maximum of {HEADER_LEN}"
+            ),
+            Self::Overflow => write!(f, "shared buffer size overflowed usize"),
+            Self::Header => write!(f, "file is not a shared cell buffer"),
+            Self::ElementSize { expected, found } => write!(
+                f,
+                "element size {found} does not match the expected size {expected}"
+            ),
+            Self::ElementAlignment { expected, found } => write!(
+                f,
+                "element alignment {found} does not match the expected alignment {expected}"
+            ),
+            Self::Length { len, size } => write!(
+                f,
+                "shared buffer of length {len} does not fit in a file of {size} bytes"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SharedCellBufferError {}
+
+impl From<std::io::Error> for SharedCellBufferError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// A buffer of [`Pod`] elements in anonymous shared memory that can be mapped by multiple processes.
+///
+/// The buffer is backed by a `memfd` file that starts with a 64-byte header recording the element size, alignment, and length.
+/// The file descriptor can be passed to child processes (e.g. inherited with `fork`, or sent over a Unix socket), which map the same memory with [`SharedCellBuffer::attach`].
+/// The header is validated on attach, so a buffer cannot be attached with a different element type layout.
+/// The file is sealed against shrinking, so another process cannot truncate it under the mapping.
+///
+/// ```rust
+/// # use unsafe_cell_slice::SharedCellBuffer;
+/// let mut buffer = SharedCellBuffer::<u32>::create(4)?;
+/// let fd = buffer.fd().try_clone_to_owned()?;
+/// // e.g. in a child process
+/// let mut attached = unsafe { SharedCellBuffer::<u32>::attach(fd) }?;
+/// unsafe { attached.as_slice().index_mut(2..) }.fill(7);
+/// assert_eq!(unsafe { buffer.as_slice().index_mut(..) }, [0, 0, 7, 7]);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug)]
+pub struct SharedCellBuffer<T: Pod> {
+    fd: OwnedFd,
+    map: NonNull<u8>,
+    map_len: usize,
+    len: usize,
+    _marker: PhantomData<T>,
+}
+
+unsafe impl<T: Pod> Send for SharedCellBuffer<T> {}
+unsafe impl<T: Pod> Sync for SharedCellBuffer<T> {}
+
+impl<T: Pod> SharedCellBuffer<T> {
+    /// Create a new [`SharedCellBuffer`] of `len` zeroed elements.
+    ///
+    /// The file descriptor is not close-on-exec, so it is inherited by child processes.
+    ///
+    /// # Errors
+    /// Returns a [`SharedCellBufferError`] if the alignment of `T` exceeds 64 bytes, the size of the buffer overflows, or the shared memory cannot be created or mapped.
+    pub fn create(len: usize) -> Result<Self, SharedCellBufferError> {
+        let map_len = mapping_len::<T>(len)?;
+        // SAFETY: the name is a nul-terminated string
+        let fd = unsafe {
+            libc::memfd_create(
+                b"unsafe_cell_slice\0".as_ptr().cast(),
+                libc::MFD_ALLOW_SEALING,
+            )
+        };
+        if fd < 0 {
+            return Err(std::io::Error::last_os_error().into());
+        }
+        // SAFETY: the file descriptor was just created and is owned
+        let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+        let file = File::from(fd);
+        file.set_len(map_len as u64)?;
+        let fd = OwnedFd::from(file);
+        // SAFETY: the file descriptor is a valid memfd that allows sealing
+        if unsafe { libc::fcntl(fd.as_raw_fd(), libc::F_ADD_SEALS, libc::F_SEAL_SHRINK) } < 0 {
+            return Err(std::io::Error::last_os_error().into());
+        }
+        let map = map_shared(&fd, map_len)?;
+        let header = Header {
+            magic: MAGIC,
+            elem_size: core::mem::size_of::<T>() as u64,
+            elem_align: core::mem::align_of::<T>() as u64,
+            len: len as u64,
+        };
+        // SAFETY: the mapping is page aligned and longer than the header
+        unsafe { map.as_ptr().cast::<Header>().write(header) };
+        Ok(Self {
+            fd,
+            map,
+            map_len,
+            len,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Attach to a [`SharedCellBuffer`] created in this or another process.
+    ///
+    /// # Errors
+    /// Returns a [`SharedCellBufferError`] if the header is missing or does not match the element type, the file is too short, or the file cannot be mapped.
+    ///
+    /// # Safety
+    /// The file must not be truncated while it is mapped.
+    /// This holds for a buffer from [`SharedCellBuffer::create`], which is sealed against shrinking.
+    pub unsafe fn attach(fd: OwnedFd) -> Result<Self, SharedCellBufferError> {
+        let file = File::from(fd);
+        let size = file.metadata()?.len();
+        let fd = OwnedFd::from(file);
+        if size < HEADER_LEN as u64 {
+            return Err(SharedCellBufferError::Header);
+        }
+        let header = {
+            let header_map = map_shared(&fd, HEADER_LEN)?;
+            let header = header_map.as_ptr().cast::<Header>().read();
+            unmap(header_map, HEADER_LEN);
+            header
+        };
+        if header.magic != MAGIC {
+            return Err(SharedCellBufferError::Header);
+        }
+        if header.elem_size != core::mem::size_of::<T>() as u64 {
+            return Err(SharedCellBufferError::ElementSize {
+                expected: core::mem::size_of::<T>(),
+                found: header.elem_size,
+            });
+        }
+        if header.elem_align != core::mem::align_of::<T>() as u64 {
+            return Err(SharedCellBufferError::ElementAlignment {
+                expected: core::mem::align_of::<T>(),
+                found: header.elem_align,
+            });
+        }
+        let map_len = usize::try_from(header.len)
+            .ok()
+            .and_then(|len| mapping_len::<T>(len).ok())
+            .filter(|&map_len| map_len as u64 <= size)
+            .ok_or(SharedCellBufferError::Length {
+                len: header.len,
+                size,
+            })?;
+        let map = map_shared(&fd, map_len)?;
+        Ok(Self {
+            fd,
+            map,
+            map_len,
+            len: header.len as usize,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Return the file descriptor of the shared memory.
+    #[must_use]
+    pub fn fd(&self) -> BorrowedFd<'_> {
+        self.fd.as_fd()
+    }
+
+    /// Return the number of elements in the buffer.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return whether the buffer is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Return an [`UnsafeCellSlice`] of the elements of the buffer.
+    ///
+    /// Other processes attached to the buffer can access the same elements.
+    /// It is the responsibility of the caller to coordinate processes so that they only access non-overlapping elements.
+    #[must_use]
+    pub fn as_slice(&mut self) -> UnsafeCellSlice<'_, T> {
+        // SAFETY: the mapping holds `len` elements after the header, which is aligned for `T`, and is exclusively borrowed in this process
+        unsafe {
+            let data = NonNull::new_unchecked(self.map.as_ptr().add(HEADER_LEN)).cast::<T>();
+            UnsafeCellSlice::from_raw_parts(data, self.len)
+        }
+    }
+}
+
+impl<T: Pod> Drop for SharedCellBuffer<T> {
+    fn drop(&mut self) {
+        unmap(self.map, self.map_len);
+    }
+}
+
+impl<T: Pod> AsFd for SharedCellBuffer<T> {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.fd()
+    }
+}
+
+impl<T: Pod> AsRawFd for SharedCellBuffer<T> {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd.as_raw_fd()
+    }
+}
+
+/// Return the length in bytes of the mapping of a buffer of `len` elements of type `T`.
+fn mapping_len<T>(len: usize) -> Result<usize, SharedCellBufferError> {
+    let align = core::mem::align_of::<T>();
+    if align > HEADER_LEN {
+        return Err(SharedCellBufferError::Alignment(align));
+    }
+    core::mem::size_of::<T>()
+        .checked_mul(len)
+        .and_then(|size| size.checked_add(HEADER_LEN))
+        .filter(|&size| i64::try_from(size).is_ok())
+        .ok_or(SharedCellBufferError::Overflow)
+}
+
+/// Map the first `len` bytes of `fd` for shared reading and writing.
+fn map_shared(fd: &OwnedFd, len: usize) -> std::io::Result<NonNull<u8>> {
+    // SAFETY: a new mapping is created at an address chosen by the kernel
+    let ptr = unsafe {
+        libc::mmap(
+            core::ptr::null_mut(),
+            len,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_SHARED,
+            fd.as_raw_fd(),
+            0,
+        )
+    };
+    if ptr == libc::MAP_FAILED {
+        Err(std::io::Error::last_os_error())
+    } else {
+        Ok(NonNull::new(ptr.cast::<u8>()).expect("mapping is non-null"))
+    }
+}
+
+/// Unmap a mapping of `len` bytes created by [`map_shared`].
+fn unmap(map: NonNull<u8>, len: usize) {
+    // SAFETY: the mapping was created by `map` with the same length and is no longer referenced
+    unsafe { libc::munmap(map.as_ptr().cast(), len) };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::os::unix::fs::FileExt;
+
+    use super::*;
+
+    #[test]
+    fn shm_shared() {
+        let mut buffer = SharedCellBuffer::<u64>::create(8).unwrap();
+        assert_eq!(buffer.len(), 8);
+        let mut attached =
+            unsafe { SharedCellBuffer::<u64>::attach(buffer.fd().try_clone_to_owned().unwrap()) }
+                .unwrap();
+        assert_eq!(attached.len(), 8);
+        {
+            let (a, b) = (buffer.as_slice(), attached.as_slice());
+            std::thread::scope(|s| {
+                s.spawn(|| unsafe { a.index_mut(..4) }.fill(1));
+                s.spawn(|| unsafe { b.index_mut(4..) }.fill(2));
+            });
+        }
+        assert_eq!(
+            unsafe { buffer.as_slice().index_mut(..) },
+            [1, 1, 1, 1, 2, 2, 2, 2]
+        );
+        drop(buffer);
+        assert_eq!(*unsafe { attached.as_slice().index_mut(0) }, 1);
+    }
+
+    #[test]
+    fn shm_attach_errors() {
+        let buffer = SharedCellBuffer::<u32>::create(3).unwrap();
+        let fd = || buffer.fd().try_clone_to_owned().unwrap();
+        assert!(matches!(
+            unsafe { SharedCellBuffer::<u16>::attach(fd()) },
+            Err(SharedCellBufferError::ElementSize {
+                expected: 2,
+                found: 4
+            })
+        ));
+        assert!(matches!(
+            unsafe { SharedCellBuffer::<[u8; 4]>::attach(fd()) },
+            Err(SharedCellBufferError::ElementAlignment {
+                expected: 1,
+                found: 4
+            })
+        ));
+        assert!(File::from(fd()).set_len(HEADER_LEN as u64).is_err());
+        let mut header = [0u8; HEADER_LEN];
+        File::from(fd()).read_exact_at(&mut header, 0).unwrap();
+        drop(buffer);
+        let path = std::env::temp_dir().join(format!(
+            "unsafe_cell_slice_shm_truncated_{}.bin",
+            std::process::id()
+        ));
+        std::fs::write(&path, [&header[..], &[0; 8]].concat()).unwrap();
+        let truncated = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&path)
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            unsafe { SharedCellBuffer::<u32>::attach(truncated.into()) },
+            Err(SharedCellBufferError::Length { len: 3, size: 72 })
+        ));
+        let file = File::open("/dev/zero").unwrap();
+        assert!(matches!(
+            unsafe { SharedCellBuffer::<u32>::attach(file.into()) },
+            Err(SharedCellBufferError::Header)
+        ));
+    }
+
+    #[test]
+    fn shm_empty() {
+        let mut buffer = SharedCellBuffer::<f32>::create(0).unwrap();
+        assert!(buffer.is_empty());
+        assert!(buffer.as_slice().is_empty());
+    }
+}
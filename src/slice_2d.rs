@@ -0,0 +1,420 @@
+use crate::UnsafeCellSlice;
+
+/// A two-dimensional unsafe cell slice with a row pitch. Permits acquisition of multiple mutable references of rows, pixels, and tiles of an image or raster.
+///
+/// Row `y` spans elements `y * pitch..y * pitch + width` of the underlying slice.
+///
+/// This is inherently unsafe.
+/// It is the responsibility of the caller to only access non-overlapping subslices/elements to avoid data races and undefined behavior.
+pub struct UnsafeCellSlice2D<'a, T> {
+    data: UnsafeCellSlice<'a, T>,
+    width: usize,
+    height: usize,
+    pitch: usize,
+}
+
+impl<T> Clone for UnsafeCellSlice2D<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for UnsafeCellSlice2D<'_, T> {}
+
+impl<'a, T> UnsafeCellSlice2D<'a, T> {
+    /// Create a new [`UnsafeCellSlice2D`] with `height` rows of `width` elements separated by `pitch` elements.
+    ///
+    /// # Panics
+    /// Panics if `pitch` is less than `width` or `data` is too short.
+    #[must_use]
+    pub fn new(data: UnsafeCellSlice<'a, T>, width: usize, height: usize, pitch: usize) -> Self {
+        assert!(pitch >= width, "pitch must not be less than width");
+        let len = span_len(width, height, pitch).expect("image size overflowed usize");
+        assert!(len <= data.len(), "slice is too short for the image");
+        Self {
+            data: data.subslice(0, len),
+            width,
+            height,
+            pitch,
+        }
+    }
+
+    /// Return the width in elements.
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Return the height in rows.
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Return the row pitch in elements.
+    #[must_use]
+    pub fn pitch(&self) -> usize {
+        self.pitch
+    }
+
+    /// Get a mutable reference to row `y`.
+    ///
+    /// Returns `None` if the row is out of bounds.
+    ///
+    /// # Safety
+    /// This is very unsafe because it is capable of creating multiple mutable references to the same data.
+    /// It is the responsibility of the caller to only access non-overlapping subslices to avoid data races and undefined behavior.
+    #[must_use]
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn get_row_mut(&self, y: usize) -> Option<&mut [T]> {
+        if y < self.height {
+            let start = y * self.pitch;
+            self.data.get_mut(start..start + self.width)
+        } else {
+            None
+        }
+    }
+
+    /// Get a mutable reference to row `y`.
+    ///
+    /// # Safety
+    /// This is very unsafe because it is capable of creating multiple mutable references to the same data.
+    /// It is the responsibility of the caller to only access non-overlapping subslices to avoid data races and undefined behavior.
+    ///
+    /// # Panics
+    /// Panics if the row is out of bounds.
+    #[must_use]
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn row_mut(&self, y: usize) -> &mut [T] {
+        self.get_row_mut(y).expect("row out of bounds")
+    }
+
+    /// Get a mutable reference to the element at (`x`, `y`).
+    ///
+    /// Returns `None` if the element is out of bounds.
+    ///
+    /// # Safety
+    /// This is very unsafe because it is capable of creating multiple mutable references to the same data.
+    /// It is the responsibility of the caller to only access non-overlapping subslices to avoid data races and undefined behavior.
+    #[must_use]
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn get_pixel_mut(&self, x: usize, y: usize) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            self.data.get_mut(y * self.pitch + x)
+        } else {
+            None
+        }
+    }
+
+    /// Get a mutable reference to the element at (`x`, `y`).
+    ///
+    /// # Safety
+    /// This is very unsafe because it is capable of creating multiple mutable references to the same data.
+    /// It is the responsibility of the caller to only access non-overlapping subslices to avoid data races and undefined behavior.
+    ///
+    /// # Panics
+    /// Panics if the element is out of bounds.
+    #[must_use]
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn pixel_mut(&self, x: usize, y: usize) -> &mut T {
+        self.get_pixel_mut(x, y).expect("pixel out of bounds")
+    }
+
+    /// Return a view of the `width` by `height` tile with its top left element at (`x`, `y`).
+    ///
+    /// Returns `None` if the tile is out of bounds.
+    #[must_use]
+    pub fn get_tile(&self, x: usize, y: usize, width: usize, height: usize) -> Option<Self> {
+        let in_bounds =
+            x.checked_add(width)? <= self.width && y.checked_add(height)? <= self.height;
+        if !in_bounds {
+            return None;
+        }
+        let start = if width == 0 || height == 0 {
+            0
+        } else {
+            y * self.pitch + x
+        };
+        let len = span_len(width, height, self.pitch)?;
+        Some(Self {
+            data: self.data.subslice(start, start + len),
+            width,
+            height,
+            pitch: self.pitch,
+        })
+    }
+
+    /// Return a view of the `width` by `height` tile with its top left element at (`x`, `y`).
+    ///
+    /// # Panics
+    /// Panics if the tile is out of bounds.
+    #[must_use]
+    pub fn tile(&self, x: usize, y: usize, width: usize, height: usize) -> Self {
+        self.get_tile(x, y, width, height)
+            .expect("tile out of bounds")
+    }
+
+    /// Return an iterator over disjoint tiles of at most `tile_width` by `tile_height` elements in row-major order.
+    ///
+    /// Tiles on the right and bottom edges are smaller if the image dimensions are not multiples of the tile dimensions.
+    ///
+    /// # Safety
+    /// The tiles permit safe mutable access to all elements of the image.
+    /// It is the responsibility of the caller to not access the elements of the image through any other reference while the tiles exist.
+    ///
+    /// # Panics
+    /// Panics if `tile_width` or `tile_height` is zero.
+    #[must_use]
+    pub unsafe fn tiles(&self, tile_width: usize, tile_height: usize) -> Tiles<'a, T> {
+        assert!(
+            tile_width != 0 && tile_height != 0,
+            "tile dimensions must be non-zero"
+        );
+        let tiles_x = self.width / tile_width + usize::from(self.width % tile_width != 0);
+        let tiles_y = self.height / tile_height + usize::from(self.height % tile_height != 0);
+        Tiles {
+            image: Self {
+                data: self.data,
+                ..*self
+            },
+            tile_width,
+            tile_height,
+            tiles_x,
+            index: 0,
+            len: tiles_x * tiles_y,
+        }
+    }
+}
+
+/// Return the number of elements spanned by `height` rows of `width` elements separated by `pitch` elements.
+fn span_len(width: usize, height: usize, pitch: usize) -> Option<usize> {
+    if width == 0 || height == 0 {
+        Some(0)
+    } else {
+        (height - 1).checked_mul(pitch)?.checked_add(width)
+    }
+}
+
+/// A tile of an [`UnsafeCellSlice2D`] with safe mutable access to its elements.
+///
+/// Created by [`Tiles`].
+pub struct TileMut<'a, T> {
+    tile: UnsafeCellSlice2D<'a, T>,
+    x: usize,
+    y: usize,
+}
+
+impl<T> TileMut<'_, T> {
+    /// Return the x coordinate of the top left element of the tile in the image.
+    #[must_use]
+    pub fn x(&self) -> usize {
+        self.x
+    }
+
+    /// Return the y coordinate of the top left element of the tile in the image.
+    #[must_use]
+    pub fn y(&self) -> usize {
+        self.y
+    }
+
+    /// Return the width of the tile in elements.
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.tile.width
+    }
+
+    /// Return the height of the tile in rows.
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.tile.height
+    }
+
+    /// Get a mutable reference to row `y` of the tile, or `None` if out of bounds.
+    #[must_use]
+    pub fn get_row_mut(&mut self, y: usize) -> Option<&mut [T]> {
+        // SAFETY: the tile has exclusive access to its elements and is mutably borrowed
+        unsafe { self.tile.get_row_mut(y) }
+    }
+
+    /// Get a mutable reference to row `y` of the tile.
+    ///
+    /// # Panics
+    /// Panics if the row is out of bounds.
+    #[must_use]
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        self.get_row_mut(y).expect("row out of bounds")
+    }
+
+    /// Get a mutable reference to the element at (`x`, `y`) of the tile, or `None` if out of bounds.
+    #[must_use]
+    pub fn get_pixel_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        // SAFETY: the tile has exclusive access to its elements and is mutably borrowed
+        unsafe { self.tile.get_pixel_mut(x, y) }
+    }
+
+    /// Get a mutable reference to the element at (`x`, `y`) of the tile.
+    ///
+    /// # Panics
+    /// Panics if the element is out of bounds.
+    #[must_use]
+    pub fn pixel_mut(&mut self, x: usize, y: usize) -> &mut T {
+        self.get_pixel_mut(x, y).expect("pixel out of bounds")
+    }
+}
+
+/// An iterator over disjoint [`TileMut`]s of an image in row-major order.
+///
+/// ```rust
+/// # use rayon::prelude::*;
+/// # use unsafe_cell_slice::Tiles;
+/// let (width, height, pitch) = (5, 3, 8);
+/// let mut image = vec![0u8; pitch * height];
+/// let tiles: Vec<_> = Tiles::new(&mut image, width, height, pitch, 2, 2).collect();
+/// assert_eq!(tiles.len(), 6);
+/// tiles.into_par_iter().for_each(|mut tile| {
+///     for y in 0..tile.height() {
+///         tile.row_mut(y).fill(1);
+///     }
+/// });
+/// assert_eq!(image.iter().filter(|v| **v == 1).count(), width * height);
+/// ```
+pub struct Tiles<'a, T> {
+    image: UnsafeCellSlice2D<'a, T>,
+    tile_width: usize,
+    tile_height: usize,
+    tiles_x: usize,
+    index: usize,
+    len: usize,
+}
+
+impl<'a, T> Tiles<'a, T> {
+    /// Create an iterator over disjoint tiles of at most `tile_width` by `tile_height` elements of an image with `height` rows of `width` elements separated by `pitch` elements.
+    ///
+    /// # Panics
+    /// Panics if `pitch` is less than `width`, `data` is too short, or `tile_width` or `tile_height` is zero.
+    #[must_use]
+    pub fn new(
+        data: &'a mut [T],
+        width: usize,
+        height: usize,
+        pitch: usize,
+        tile_width: usize,
+        tile_height: usize,
+    ) -> Self {
+        let image = UnsafeCellSlice2D::new(UnsafeCellSlice::new(data), width, height, pitch);
+        // SAFETY: the image is exclusively borrowed for the lifetime of the tiles
+        unsafe { image.tiles(tile_width, tile_height) }
+    }
+}
+
+impl<'a, T> Iterator for Tiles<'a, T> {
+    type Item = TileMut<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == self.len {
+            return None;
+        }
+        let x = (self.index % self.tiles_x) * self.tile_width;
+        let y = (self.index / self.tiles_x) * self.tile_height;
+        self.index += 1;
+        let width = self.tile_width.min(self.image.width - x);
+        let height = self.tile_height.min(self.image.height - y);
+        Some(TileMut {
+            tile: self.image.tile(x, y, width, height),
+            x,
+            y,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len - self.index;
+        (len, Some(len))
+    }
+}
+
+impl<T> ExactSizeIterator for Tiles<'_, T> {}
+
+#[cfg(feature = "image")]
+impl<'a, P, C> From<&'a mut image::ImageBuffer<P, C>> for UnsafeCellSlice2D<'a, P::Subpixel>
+where
+    P: image::Pixel,
+    C: core::ops::DerefMut<Target = [P::Subpixel]>,
+{
+    /// Create an [`UnsafeCellSlice2D`] of the subpixels of an [`image::ImageBuffer`].
+    ///
+    /// The width is the image width multiplied by the number of channels.
+    fn from(image: &'a mut image::ImageBuffer<P, C>) -> Self {
+        let (width, height) = image.dimensions();
+        let width = width as usize * usize::from(P::CHANNEL_COUNT);
+        let height = height as usize;
+        Self::new(UnsafeCellSlice::new(image), width, height, width)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::vec::Vec;
+
+    #[test]
+    fn slice_2d() {
+        let mut data: Vec<u32> = (0..12).collect();
+        let data = UnsafeCellSlice::new(&mut data);
+        let image = UnsafeCellSlice2D::new(data, 3, 3, 4);
+        assert_eq!((image.width(), image.height(), image.pitch()), (3, 3, 4));
+        assert_eq!(unsafe { image.row_mut(1) }, [4, 5, 6]);
+        assert!(unsafe { image.get_row_mut(3) }.is_none());
+        assert_eq!(*unsafe { image.pixel_mut(2, 2) }, 10);
+        assert!(unsafe { image.get_pixel_mut(3, 0) }.is_none());
+        let tile = image.tile(1, 1, 2, 2);
+        assert_eq!(unsafe { tile.row_mut(0) }, [5, 6]);
+        assert_eq!(unsafe { tile.row_mut(1) }, [9, 10]);
+        assert_eq!(*unsafe { tile.pixel_mut(1, 0) }, 6);
+        assert!(image.get_tile(2, 0, 2, 1).is_none());
+        assert!(image.get_tile(0, 0, 3, 4).is_none());
+        assert!(image.get_tile(3, 3, 0, 0).is_some());
+    }
+
+    #[test]
+    #[should_panic]
+    fn slice_2d_too_short() {
+        let mut data = vec![0u8; 10];
+        let _ = UnsafeCellSlice2D::new(UnsafeCellSlice::new(&mut data), 3, 3, 4);
+    }
+
+    #[test]
+    fn tiles() {
+        let (width, height, pitch) = (5, 5, 6);
+        let mut data = vec![0usize; pitch * height];
+        let tiles = Tiles::new(&mut data, width, height, pitch, 2, 3);
+        assert_eq!(tiles.len(), 6);
+        for (i, mut tile) in tiles.enumerate() {
+            assert_eq!(tile.x(), (i % 3) * 2);
+            assert_eq!(tile.y(), (i / 3) * 3);
+            for y in 0..tile.height() {
+                tile.row_mut(y).fill(i + 1);
+            }
+            assert!(tile.get_pixel_mut(tile.width(), 0).is_none());
+        }
+        assert_eq!(&data[0..6], [1, 1, 2, 2, 3, 0]);
+        assert_eq!(&data[24..30], [4, 4, 5, 5, 6, 0]);
+
+        let mut units = [(); usize::MAX];
+        let view =
+            UnsafeCellSlice2D::new(UnsafeCellSlice::new(&mut units), usize::MAX, 1, usize::MAX);
+        assert_eq!(unsafe { view.tiles(2, 2) }.len(), usize::MAX / 2 + 1);
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn slice_2d_image() {
+        let mut image = image::RgbImage::new(3, 2);
+        {
+            let view = UnsafeCellSlice2D::from(&mut image);
+            assert_eq!((view.width(), view.height(), view.pitch()), (9, 2, 9));
+            unsafe { view.row_mut(1) }.fill(255);
+        }
+        assert_eq!(image.get_pixel(2, 1).0, [255, 255, 255]);
+        assert_eq!(image.get_pixel(2, 0).0, [0, 0, 0]);
+    }
+}
@@ -1,141 +1,311 @@
 use crate::UnsafeCellSlice;
 
-mod private_slice_index {
-    pub trait Sealed {}
-}
-
 /// A trait for indexing into an [`UnsafeCellSlice`].
 ///
+/// This is implemented for [`usize`] and all [`usize`] range types, and can be implemented for custom index types.
+/// Implementations should delegate to [`UnsafeCellSlice::get_mut`] or [`UnsafeCellSlice::get_subslice_mut`] rather than using raw pointers.
+///
+/// ```rust
+/// # use unsafe_cell_slice::{SliceIndex, UnsafeCellSlice};
+/// /// Chunk `index` of `size` elements.
+/// struct Chunk {
+///     index: usize,
+///     size: usize,
+/// }
+///
+/// unsafe impl<T> SliceIndex<T> for Chunk {
+///     type Output = [T];
+///
+///     unsafe fn get_mut<'a>(self, slice: &'a UnsafeCellSlice<T>) -> Option<&'a mut [T]> {
+///         slice.get_subslice_mut(self.index.checked_mul(self.size)?, self.size)
+///     }
+///
+///     unsafe fn index_mut<'a>(self, slice: &'a UnsafeCellSlice<T>) -> &'a mut [T] {
+///         self.get_mut(slice).expect("chunk out of bounds")
+///     }
+/// }
+///
+/// let mut data = vec![0u8; 6];
+/// let slice = UnsafeCellSlice::new(&mut data);
+/// unsafe { slice.index_mut(Chunk { index: 1, size: 2 }) }.fill(1);
+/// assert!(unsafe { slice.get_mut(Chunk { index: 3, size: 2 }) }.is_none());
+/// assert_eq!(data, [0, 0, 1, 1, 0, 0]);
+/// ```
+///
 /// # Safety
+/// Implementations must:
+///  - only return references to elements of `slice`,
+///  - be deterministic, such that an index always refers to the same elements of a slice with a given length, and
+///  - not create references to elements other than those returned.
+///
 /// Callers of the trait methods must ensure that overlapping subslices/elements are not requested.
-pub unsafe trait SliceIndex<T>: private_slice_index::Sealed {
+pub unsafe trait SliceIndex<T> {
+    /// The output type, typically `T` or `[T]`.
     type Output: ?Sized;
 
+    /// Returns a mutable reference to the output at this location, or an [`IndexError`] if out of bounds.
+    ///
+    /// The default implementation returns [`IndexError::OutOfRange`] if [`get_mut`](SliceIndex::get_mut) returns `None`.
+    ///
+    /// # Safety
+    /// The caller must ensure that overlapping subslices/elements are not requested.
+    #[allow(clippy::mut_from_ref)]
+    unsafe fn try_get_mut<'a>(
+        self,
+        slice: &'a UnsafeCellSlice<T>,
+    ) -> Result<&'a mut Self::Output, IndexError>
+    where
+        Self: Sized,
+    {
+        let len = slice.len();
+        self.get_mut(slice).ok_or(IndexError::OutOfRange { len })
+    }
+
     /// Returns a mutable reference to the output at this location, if in bounds.
-    fn get_mut<'a>(self, slice: &'a UnsafeCellSlice<T>) -> Option<&'a mut Self::Output>;
+    ///
+    /// # Safety
+    /// The caller must ensure that overlapping subslices/elements are not requested.
+    // Recent clippy releases also lint trait method declarations for `mut_from_ref`.
+    #[allow(clippy::mut_from_ref)]
+    unsafe fn get_mut<'a>(self, slice: &'a UnsafeCellSlice<T>) -> Option<&'a mut Self::Output>;
 
     /// Returns a mutable reference to the output at this location, panicking
     /// if out of bounds.
+    ///
+    /// # Safety
+    /// The caller must ensure that overlapping subslices/elements are not requested.
     #[allow(clippy::mut_from_ref)]
-    fn index_mut<'a>(self, slice: &'a UnsafeCellSlice<T>) -> &'a mut Self::Output;
+    unsafe fn index_mut<'a>(self, slice: &'a UnsafeCellSlice<T>) -> &'a mut Self::Output;
 }
 
-// impl private_slice_index::Sealed for (Bound<usize>, Bound<usize>) {}
-impl private_slice_index::Sealed for usize {}
-impl private_slice_index::Sealed for std::ops::Range<usize> {}
-impl private_slice_index::Sealed for std::ops::RangeFrom<usize> {}
-impl private_slice_index::Sealed for std::ops::RangeFull {}
-impl private_slice_index::Sealed for std::ops::RangeInclusive<usize> {}
-impl private_slice_index::Sealed for std::ops::RangeTo<usize> {}
-impl private_slice_index::Sealed for std::ops::RangeToInclusive<usize> {}
-
-// TODO (Bound<usize>, Bound<usize>) is not implemented
-
-unsafe impl<T> SliceIndex<T> for usize {
-    type Output = T;
-
-    fn get_mut<'a>(self, slice: &'a UnsafeCellSlice<T>) -> Option<&'a mut Self::Output> {
-        slice.0.get(self).map(|v| unsafe { &mut *v.get() })
+impl<T> UnsafeCellSlice<'_, T> {
+    /// Get a mutable reference to the subslice of `len` elements starting at `start`.
+    ///
+    /// Returns `None` if the subslice is out of bounds.
+    ///
+    /// # Safety
+    /// This is very unsafe because it is capable of creating multiple mutable references to the same data.
+    /// It is the responsibility of the caller to only access non-overlapping subslices to avoid data races and undefined behavior.
+    #[must_use]
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn get_subslice_mut(&self, start: usize, len: usize) -> Option<&mut [T]> {
+        self.get_mut(start..start.checked_add(len)?)
     }
 
-    fn index_mut<'a>(self, slice: &'a UnsafeCellSlice<T>) -> &'a mut Self::Output {
-        self.get_mut(slice).expect("index out of bounds")
+    /// Get a mutable reference to the subslice of `len` elements starting at `start`.
+    ///
+    /// # Safety
+    /// This is very unsafe because it is capable of creating multiple mutable references to the same data.
+    /// It is the responsibility of the caller to only access non-overlapping subslices to avoid data races and undefined behavior.
+    ///
+    /// # Panics
+    /// Panics if the subslice is out of bounds.
+    #[must_use]
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn index_subslice_mut(&self, start: usize, len: usize) -> &mut [T] {
+        self.get_subslice_mut(start, len)
+            .expect("index out of bounds")
     }
 }
 
-unsafe impl<T> SliceIndex<T> for std::ops::Range<usize> {
-    type Output = [T];
+/// An error indexing into an [`UnsafeCellSlice`].
+///
+/// The [`Display`](core::fmt::Display) implementation matches the panic messages of [`slice`] indexing.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum IndexError {
+    /// An element index is out of bounds.
+    OutOfBounds {
+        /// The element index.
+        index: usize,
+        /// The length of the slice.
+        len: usize,
+    },
+    /// The start of a range is after its end.
+    StartAfterEnd {
+        /// The start of the range.
+        start: usize,
+        /// The exclusive end of the range.
+        end: usize,
+    },
+    /// The start of a range is out of bounds.
+    StartOutOfRange {
+        /// The start of the range.
+        start: usize,
+        /// The length of the slice.
+        len: usize,
+    },
+    /// The end of a range is out of bounds.
+    EndOutOfRange {
+        /// The exclusive end of the range.
+        end: usize,
+        /// The length of the slice.
+        len: usize,
+    },
+    /// The inclusive end of a range is [`usize::MAX`].
+    Overflow,
+    /// A custom [`SliceIndex`] is out of bounds.
+    OutOfRange {
+        /// The length of the slice.
+        len: usize,
+    },
+}
 
-    fn get_mut<'a>(self, slice: &'a UnsafeCellSlice<T>) -> Option<&'a mut Self::Output> {
-        slice
-            .0
-            .get(self)
-            .map(|s| unsafe { std::slice::from_raw_parts_mut(s.as_ptr() as *mut T, s.len()) })
+impl core::fmt::Display for IndexError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::OutOfBounds { index, len } => write!(
+                f,
+                "index out of bounds: the len is {len} but the index is {index}"
+            ),
+            Self::StartAfterEnd { start, end } => {
+                write!(f, "slice index starts at {start} but ends at {end}")
+            }
+            Self::StartOutOfRange { start, len } => write!(
+                f,
+                "range start index {start} out of range for slice of length {len}"
+            ),
+            Self::EndOutOfRange { end, len } => write!(
+                f,
+                "range end index {end} out of range for slice of length {len}"
+            ),
+            Self::Overflow => write!(f, "attempted to index slice up to maximum usize"),
+            Self::OutOfRange { len } => {
+                write!(f, "index out of range for slice of length {len}")
+            }
+        }
     }
+}
 
-    fn index_mut<'a>(self, slice: &'a UnsafeCellSlice<T>) -> &'a mut Self::Output {
-        self.get_mut(slice).expect("index out of bounds")
+#[cfg(feature = "std")]
+impl std::error::Error for IndexError {}
+
+/// Check that `start..end` is a valid range of a slice of length `len`.
+pub(crate) fn check_range(start: usize, end: usize, len: usize) -> Result<(), IndexError> {
+    if start > end {
+        Err(IndexError::StartAfterEnd { start, end })
+    } else if end > len {
+        Err(IndexError::EndOutOfRange { end, len })
+    } else {
+        Ok(())
     }
 }
 
-unsafe impl<T> SliceIndex<T> for std::ops::RangeFrom<usize> {
-    type Output = [T];
-
-    fn get_mut<'a>(self, slice: &'a UnsafeCellSlice<T>) -> Option<&'a mut Self::Output> {
-        slice
-            .0
-            .get(self)
-            .map(|s| unsafe { std::slice::from_raw_parts_mut(s.as_ptr() as *mut T, s.len()) })
-    }
+/// Convert an inclusive range end to an exclusive range end.
+fn exclusive_end(end: usize) -> Result<usize, IndexError> {
+    end.checked_add(1).ok_or(IndexError::Overflow)
+}
 
-    fn index_mut<'a>(self, slice: &'a UnsafeCellSlice<T>) -> &'a mut Self::Output {
-        self.get_mut(slice).expect("index out of bounds")
-    }
+/// Get a mutable reference to the subslice `start..end` of `slice`, which must be in bounds.
+///
+/// # Safety
+/// The caller must ensure that overlapping subslices/elements are not requested.
+#[allow(clippy::mut_from_ref)]
+unsafe fn range_mut<'a, T>(slice: &'a UnsafeCellSlice<T>, start: usize, end: usize) -> &'a mut [T] {
+    let ptr = slice.as_mut_ptr().add(start);
+    #[cfg(loom)]
+    crate::loom::track_mut(ptr, end - start);
+    core::slice::from_raw_parts_mut(ptr, end - start)
 }
 
-unsafe impl<T> SliceIndex<T> for std::ops::RangeFull {
-    type Output = [T];
+// TODO (Bound<usize>, Bound<usize>) is not implemented
 
-    fn get_mut<'a>(self, slice: &'a UnsafeCellSlice<T>) -> Option<&'a mut Self::Output> {
-        slice
-            .0
-            .get(self)
-            .map(|s| unsafe { std::slice::from_raw_parts_mut(s.as_ptr() as *mut T, s.len()) })
-    }
+unsafe impl<T> SliceIndex<T> for usize {
+    type Output = T;
 
-    fn index_mut<'a>(self, slice: &'a UnsafeCellSlice<T>) -> &'a mut Self::Output {
-        self.get_mut(slice).expect("index out of bounds")
+    unsafe fn try_get_mut<'a>(
+        self,
+        slice: &'a UnsafeCellSlice<T>,
+    ) -> Result<&'a mut Self::Output, IndexError> {
+        if self < slice.len() {
+            let ptr = slice.as_mut_ptr().add(self);
+            #[cfg(loom)]
+            crate::loom::track_mut(ptr, 1);
+            Ok(&mut *ptr)
+        } else {
+            Err(IndexError::OutOfBounds {
+                index: self,
+                len: slice.len(),
+            })
+        }
     }
-}
-
-unsafe impl<T> SliceIndex<T> for std::ops::RangeInclusive<usize> {
-    type Output = [T];
 
-    fn get_mut<'a>(self, slice: &'a UnsafeCellSlice<T>) -> Option<&'a mut Self::Output> {
-        slice
-            .0
-            .get(self)
-            .map(|s| unsafe { std::slice::from_raw_parts_mut(s.as_ptr() as *mut T, s.len()) })
+    unsafe fn get_mut<'a>(self, slice: &'a UnsafeCellSlice<T>) -> Option<&'a mut Self::Output> {
+        self.try_get_mut(slice).ok()
     }
 
-    fn index_mut<'a>(self, slice: &'a UnsafeCellSlice<T>) -> &'a mut Self::Output {
-        self.get_mut(slice).expect("index out of bounds")
+    unsafe fn index_mut<'a>(self, slice: &'a UnsafeCellSlice<T>) -> &'a mut Self::Output {
+        self.try_get_mut(slice)
+            .unwrap_or_else(|err| panic!("{err}"))
     }
 }
 
-unsafe impl<T> SliceIndex<T> for std::ops::RangeTo<usize> {
-    type Output = [T];
+macro_rules! impl_slice_index_range {
+    ($range:ty, |$self:ident, $len:ident| $bounds:expr) => {
+        unsafe impl<T> SliceIndex<T> for $range {
+            type Output = [T];
+
+            unsafe fn try_get_mut<'a>(
+                self,
+                slice: &'a UnsafeCellSlice<T>,
+            ) -> Result<&'a mut Self::Output, IndexError> {
+                let ($self, $len) = (self, slice.len());
+                let (start, end): (usize, usize) = $bounds;
+                Ok(range_mut(slice, start, end))
+            }
+
+            unsafe fn get_mut<'a>(
+                self,
+                slice: &'a UnsafeCellSlice<T>,
+            ) -> Option<&'a mut Self::Output> {
+                self.try_get_mut(slice).ok()
+            }
+
+            unsafe fn index_mut<'a>(self, slice: &'a UnsafeCellSlice<T>) -> &'a mut Self::Output {
+                self.try_get_mut(slice)
+                    .unwrap_or_else(|err| panic!("{err}"))
+            }
+        }
+    };
+}
 
-    fn get_mut<'a>(self, slice: &'a UnsafeCellSlice<T>) -> Option<&'a mut Self::Output> {
-        slice
-            .0
-            .get(self)
-            .map(|s| unsafe { std::slice::from_raw_parts_mut(s.as_ptr() as *mut T, s.len()) })
+impl_slice_index_range!(core::ops::Range<usize>, |range, len| {
+    check_range(range.start, range.end, len)?;
+    (range.start, range.end)
+});
+
+impl_slice_index_range!(core::ops::RangeFrom<usize>, |range, len| {
+    if range.start > len {
+        return Err(IndexError::StartOutOfRange {
+            start: range.start,
+            len,
+        });
     }
+    (range.start, len)
+});
 
-    fn index_mut<'a>(self, slice: &'a UnsafeCellSlice<T>) -> &'a mut Self::Output {
-        self.get_mut(slice).expect("index out of bounds")
-    }
-}
+impl_slice_index_range!(core::ops::RangeFull, |_range, len| (0, len));
 
-unsafe impl<T> SliceIndex<T> for std::ops::RangeToInclusive<usize> {
-    type Output = [T];
+impl_slice_index_range!(core::ops::RangeInclusive<usize>, |range, len| {
+    let (start, end) = (*range.start(), exclusive_end(*range.end())?);
+    check_range(start, end, len)?;
+    (start, end)
+});
 
-    fn get_mut<'a>(self, slice: &'a UnsafeCellSlice<T>) -> Option<&'a mut Self::Output> {
-        slice
-            .0
-            .get(self)
-            .map(|s| unsafe { std::slice::from_raw_parts_mut(s.as_ptr() as *mut T, s.len()) })
-    }
+impl_slice_index_range!(core::ops::RangeTo<usize>, |range, len| {
+    check_range(0, range.end, len)?;
+    (0, range.end)
+});
 
-    fn index_mut<'a>(self, slice: &'a UnsafeCellSlice<T>) -> &'a mut Self::Output {
-        self.get_mut(slice).expect("index out of bounds")
-    }
-}
+impl_slice_index_range!(core::ops::RangeToInclusive<usize>, |range, len| {
+    let end = exclusive_end(range.end)?;
+    check_range(0, end, len)?;
+    (0, end)
+});
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::{string::ToString, vec::Vec};
 
     #[test]
     fn slice_index_usize() {
@@ -151,6 +321,8 @@ mod tests {
     }
 
     #[test]
+    // Recent clippy releases reject the deliberately reversed ranges checked below.
+    #[allow(clippy::reversed_empty_ranges)]
     fn slice_index_range() {
         let mut data = vec![0i64, 1i64, 2i64];
         let data = UnsafeCellSlice::new(&mut data);
@@ -222,6 +394,8 @@ mod tests {
     }
 
     #[test]
+    // The reversed inclusive ranges below are intentional; recent clippy rejects them.
+    #[allow(clippy::reversed_empty_ranges)]
     fn slice_index_range_inclusive() {
         let mut data = vec![0i64, 1i64, 2i64, 3i64];
         let data = UnsafeCellSlice::new(&mut data);
@@ -310,4 +484,110 @@ mod tests {
         assert_eq!(unsafe { data.get_mut(..=3) }.unwrap().len(), 4);
         assert!(unsafe { data.get_mut(..=4) }.is_none());
     }
+
+    #[test]
+    fn slice_index_subslice() {
+        let mut data = vec![0i64, 1i64, 2i64];
+        let data = UnsafeCellSlice::new(&mut data);
+        assert_eq!(unsafe { data.index_subslice_mut(1, 2) }, [1, 2]);
+        assert_eq!(unsafe { data.index_subslice_mut(3, 0) }, []);
+        assert!(unsafe { data.get_subslice_mut(2, 2) }.is_none());
+        assert!(unsafe { data.get_subslice_mut(1, usize::MAX) }.is_none());
+    }
+
+    #[test]
+    fn slice_index_custom() {
+        struct Pair(usize);
+
+        unsafe impl<T> SliceIndex<T> for Pair {
+            type Output = [T];
+
+            unsafe fn get_mut<'a>(self, slice: &'a UnsafeCellSlice<T>) -> Option<&'a mut [T]> {
+                slice.get_subslice_mut(self.0.checked_mul(2)?, 2)
+            }
+
+            unsafe fn index_mut<'a>(self, slice: &'a UnsafeCellSlice<T>) -> &'a mut [T] {
+                self.get_mut(slice).expect("index out of bounds")
+            }
+        }
+
+        let mut data = vec![0i64, 1i64, 2i64, 3i64];
+        let data = UnsafeCellSlice::new(&mut data);
+        let a = unsafe { data.index_mut(Pair(0)) };
+        let b = unsafe { data.index_mut(Pair(1)) };
+        assert_eq!(a, [0, 1]);
+        assert_eq!(b, [2, 3]);
+        assert!(unsafe { data.get_mut(Pair(2)) }.is_none());
+        assert!(unsafe { data.get_mut(Pair(usize::MAX)) }.is_none());
+    }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn slice_index_error() {
+        let mut data = vec![0i64, 1i64, 2i64];
+        let data = UnsafeCellSlice::new(&mut data);
+        let err = |result: Result<&mut [i64], IndexError>| result.unwrap_err();
+        assert_eq!(
+            unsafe { data.try_get_mut(3) }.unwrap_err(),
+            IndexError::OutOfBounds { index: 3, len: 3 }
+        );
+        assert_eq!(
+            err(unsafe { data.try_get_mut(2..1) }),
+            IndexError::StartAfterEnd { start: 2, end: 1 }
+        );
+        assert_eq!(
+            err(unsafe { data.try_get_mut(1..4) }),
+            IndexError::EndOutOfRange { end: 4, len: 3 }
+        );
+        assert_eq!(
+            err(unsafe { data.try_get_mut(4..) }),
+            IndexError::StartOutOfRange { start: 4, len: 3 }
+        );
+        assert_eq!(
+            err(unsafe { data.try_get_mut(3..=1) }),
+            IndexError::StartAfterEnd { start: 3, end: 2 }
+        );
+        assert_eq!(
+            err(unsafe { data.try_get_mut(0..=usize::MAX) }),
+            IndexError::Overflow
+        );
+        assert_eq!(
+            err(unsafe { data.try_get_mut(..4) }),
+            IndexError::EndOutOfRange { end: 4, len: 3 }
+        );
+        assert_eq!(
+            err(unsafe { data.try_get_mut(..=3) }),
+            IndexError::EndOutOfRange { end: 4, len: 3 }
+        );
+        assert_eq!(
+            err(unsafe { data.try_get_mut(..=usize::MAX) }),
+            IndexError::Overflow
+        );
+        assert!(unsafe { data.try_get_mut(3..) }.is_ok());
+        assert!(unsafe { data.try_get_mut(..) }.is_ok());
+        assert_eq!(
+            IndexError::StartAfterEnd { start: 2, end: 1 }.to_string(),
+            "slice index starts at 2 but ends at 1"
+        );
+        assert_eq!(
+            IndexError::StartOutOfRange { start: 4, len: 3 }.to_string(),
+            "range start index 4 out of range for slice of length 3"
+        );
+        assert_eq!(
+            IndexError::EndOutOfRange { end: 4, len: 3 }.to_string(),
+            "range end index 4 out of range for slice of length 3"
+        );
+        assert_eq!(
+            IndexError::Overflow.to_string(),
+            "attempted to index slice up to maximum usize"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "range end index 4 out of range for slice of length 3")]
+    fn slice_index_panic_message() {
+        let mut data = vec![0i64, 1i64, 2i64];
+        let data = UnsafeCellSlice::new(&mut data);
+        let _ = unsafe { data.index_mut(1..4) };
+    }
 }
@@ -0,0 +1,71 @@
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, vec::Vec};
+
+/// A field of a struct-of-arrays that derives [`UnsafeCellSoA`](https://docs.rs/unsafe_cell_slice_derive/latest/unsafe_cell_slice_derive/derive.UnsafeCellSoA.html).
+///
+/// This is implemented for [`Vec<T>`], [`Box<[T]>`](Box), `&mut [T]`, and `[T; N]`.
+pub trait SoAField {
+    /// The element type.
+    type Elem;
+
+    /// Return the elements as a mutable slice.
+    fn as_mut_slice(&mut self) -> &mut [Self::Elem];
+}
+
+#[cfg(feature = "alloc")]
+impl<T> SoAField for Vec<T> {
+    type Elem = T;
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> SoAField for Box<[T]> {
+    type Elem = T;
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T> SoAField for &mut [T] {
+    type Elem = T;
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T, const N: usize> SoAField for [T; N] {
+    type Elem = T;
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+/// A struct-of-arrays field length mismatch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SoALengthError {
+    /// The name of the mismatched field.
+    pub field: &'static str,
+    /// The length of the first field.
+    pub expected: usize,
+    /// The length of the mismatched field.
+    pub found: usize,
+}
+
+impl core::fmt::Display for SoALengthError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "field `{}` has length {} but expected {}",
+            self.field, self.found, self.expected
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SoALengthError {}
@@ -0,0 +1,225 @@
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, vec::Vec};
+
+use core::ptr::NonNull;
+
+use crate::UnsafeCellSlice;
+
+/// A contiguous container of elements that can back an [`UnsafeCellSlice`].
+///
+/// This is implemented for `[T]`, `[T; N]`, [`Vec<T>`], and [`Box<[T]>`](Box).
+/// With the `smallvec` feature, this is also implemented for [`SmallVec`](https://docs.rs/smallvec/latest/smallvec/struct.SmallVec.html).
+///
+/// Implement this for arena allocations or custom buffer types to construct an [`UnsafeCellSlice`] with [`UnsafeCellSlice::from_storage`] or [`UnsafeCellSlice::from_spare_capacity`].
+///
+/// ```rust
+/// # use unsafe_cell_slice::{CellStorage, UnsafeCellSlice};
+/// struct Buffer {
+///     data: [u8; 16],
+///     len: usize,
+/// }
+///
+/// unsafe impl CellStorage for Buffer {
+///     type Elem = u8;
+///
+///     fn as_mut_ptr(&mut self) -> *mut u8 {
+///         self.data.as_mut_ptr()
+///     }
+///
+///     fn len(&self) -> usize {
+///         self.len
+///     }
+///
+///     fn capacity(&self) -> usize {
+///         self.data.len()
+///     }
+/// }
+///
+/// let mut buffer = Buffer { data: [0; 16], len: 4 };
+/// assert_eq!(UnsafeCellSlice::from_storage(&mut buffer).len(), 4);
+/// assert_eq!(UnsafeCellSlice::from_spare_capacity(&mut buffer).len(), 12);
+/// ```
+///
+/// # Safety
+/// Implementors must ensure that:
+///  - [`as_mut_ptr`](CellStorage::as_mut_ptr) returns a non-null pointer that is aligned and valid for reads and writes of [`capacity`](CellStorage::capacity) elements for as long as the storage is mutably borrowed,
+///  - the first [`len`](CellStorage::len) elements are initialised,
+///  - [`len`](CellStorage::len) does not exceed [`capacity`](CellStorage::capacity), and
+///  - calling these methods does not move or reallocate the elements.
+pub unsafe trait CellStorage {
+    /// The element type.
+    type Elem;
+
+    /// Return a pointer to the first element.
+    fn as_mut_ptr(&mut self) -> *mut Self::Elem;
+
+    /// Return the number of initialised elements.
+    fn len(&self) -> usize;
+
+    /// Return whether there are no initialised elements.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the number of elements that the storage can hold without reallocating.
+    ///
+    /// Defaults to [`len`](CellStorage::len), which means there is no spare capacity.
+    fn capacity(&self) -> usize {
+        self.len()
+    }
+}
+
+unsafe impl<T> CellStorage for [T] {
+    type Elem = T;
+
+    fn as_mut_ptr(&mut self) -> *mut T {
+        <[T]>::as_mut_ptr(self)
+    }
+
+    fn len(&self) -> usize {
+        <[T]>::len(self)
+    }
+}
+
+unsafe impl<T, const N: usize> CellStorage for [T; N] {
+    type Elem = T;
+
+    fn as_mut_ptr(&mut self) -> *mut T {
+        <[T]>::as_mut_ptr(self)
+    }
+
+    fn len(&self) -> usize {
+        N
+    }
+}
+
+#[cfg(feature = "alloc")]
+unsafe impl<T> CellStorage for Vec<T> {
+    type Elem = T;
+
+    fn as_mut_ptr(&mut self) -> *mut T {
+        Vec::as_mut_ptr(self)
+    }
+
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn capacity(&self) -> usize {
+        Vec::capacity(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+unsafe impl<T> CellStorage for Box<[T]> {
+    type Elem = T;
+
+    fn as_mut_ptr(&mut self) -> *mut T {
+        <[T]>::as_mut_ptr(self)
+    }
+
+    fn len(&self) -> usize {
+        <[T]>::len(self)
+    }
+}
+
+#[cfg(feature = "smallvec")]
+unsafe impl<A: smallvec::Array> CellStorage for smallvec::SmallVec<A> {
+    type Elem = A::Item;
+
+    fn as_mut_ptr(&mut self) -> *mut A::Item {
+        smallvec::SmallVec::as_mut_ptr(self)
+    }
+
+    fn len(&self) -> usize {
+        smallvec::SmallVec::len(self)
+    }
+
+    fn capacity(&self) -> usize {
+        smallvec::SmallVec::capacity(self)
+    }
+}
+
+impl<'a, T> UnsafeCellSlice<'a, T> {
+    /// Create a new [`UnsafeCellSlice`] from the initialised elements of a [`CellStorage`].
+    #[must_use]
+    pub fn from_storage<S: CellStorage<Elem = T> + ?Sized>(storage: &'a mut S) -> Self {
+        let len = storage.len();
+        let ptr = storage_ptr(storage);
+        #[cfg(loom)]
+        crate::loom::reset(ptr.as_ptr(), len);
+        // SAFETY: the storage is exclusively borrowed for 'a and its first `len` elements are valid
+        unsafe { Self::from_raw_parts(ptr, len) }
+    }
+
+    /// Create a new [`UnsafeCellSlice`] from the spare capacity of a [`CellStorage`].
+    ///
+    /// The elements of the returned [`UnsafeCellSlice`] are uninitialised.
+    /// They must be written before they are read, and it is the responsibility of the caller to update the length of the storage (e.g. with [`Vec::set_len`]) after they are initialised.
+    #[must_use]
+    pub fn from_spare_capacity<S: CellStorage<Elem = T> + ?Sized>(storage: &'a mut S) -> Self {
+        let (len, capacity) = (storage.len(), storage.capacity());
+        let ptr = storage_ptr(storage);
+        // SAFETY: the storage is exclusively borrowed for 'a and elements `len..capacity` are valid for writes
+        unsafe {
+            let ptr = NonNull::new_unchecked(ptr.as_ptr().add(len));
+            #[cfg(loom)]
+            crate::loom::reset(ptr.as_ptr(), capacity - len);
+            Self::from_raw_parts(ptr, capacity - len)
+        }
+    }
+}
+
+/// Return the pointer to the first element of `storage`.
+fn storage_ptr<S: CellStorage + ?Sized>(storage: &mut S) -> NonNull<S::Elem> {
+    NonNull::new(storage.as_mut_ptr()).expect("storage pointer is non-null")
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use std::{boxed::Box, vec::Vec};
+
+    #[test]
+    fn storage_containers() {
+        let mut array = [0u8; 4];
+        let mut boxed: Box<[u8]> = vec![0; 3].into_boxed_slice();
+        let slice: &mut [u8] = &mut [0; 2];
+        assert_eq!(UnsafeCellSlice::from_storage(&mut array).len(), 4);
+        assert_eq!(UnsafeCellSlice::from_storage(&mut boxed).len(), 3);
+        assert_eq!(UnsafeCellSlice::from_storage(slice).len(), 2);
+        assert!(UnsafeCellSlice::from_spare_capacity(&mut array).is_empty());
+        assert!(UnsafeCellSlice::from_spare_capacity(&mut boxed).is_empty());
+    }
+
+    #[test]
+    fn storage_vec() {
+        let mut vec: Vec<u32> = Vec::with_capacity(4);
+        vec.push(1);
+        {
+            let data = UnsafeCellSlice::from_storage(&mut vec);
+            *unsafe { data.index_mut(0) } += 1;
+        }
+        let capacity = vec.capacity();
+        {
+            let spare = UnsafeCellSlice::from_spare_capacity(&mut vec);
+            assert_eq!(spare.len(), capacity - 1);
+            unsafe { spare.index_mut(..3) }.fill(3);
+        }
+        unsafe { vec.set_len(4) };
+        assert_eq!(vec, [2, 3, 3, 3]);
+    }
+
+    #[cfg(feature = "smallvec")]
+    #[test]
+    fn storage_smallvec() {
+        let mut inline: smallvec::SmallVec<[u16; 4]> = smallvec::smallvec![1, 2];
+        {
+            let spare = UnsafeCellSlice::from_spare_capacity(&mut inline);
+            assert_eq!(spare.len(), 2);
+            unsafe { spare.index_mut(..) }.fill(5);
+        }
+        unsafe { inline.set_len(4) };
+        assert_eq!(inline.as_slice(), [1, 2, 5, 5]);
+    }
+}
@@ -0,0 +1,386 @@
+use alloc::{string::String, vec::Vec};
+use core::{
+    mem::MaybeUninit,
+    ptr::NonNull,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+
+use crate::{jagged::exclusive_prefix_sum, UnsafeCellSlice};
+
+/// A writer of a [`String`] in independent byte regions (e.g. in parallel).
+///
+/// Each region is assigned a byte length at construction and is written via a [`StrRegion`] from [`region`](StringWriter::region).
+/// [`finish`](StringWriter::finish) checks that each region was completely filled before setting the length of the [`String`].
+/// Fragments pushed with [`StrRegion::push_str`] are already valid UTF-8, so only regions written with [`StrRegion::push_bytes`] are validated.
+/// [`finish_checked`](StringWriter::finish_checked) instead re-validates the entire [`String`].
+///
+/// ```rust
+/// # use std::fmt::Write;
+/// # use rayon::prelude::*;
+/// # use unsafe_cell_slice::StringWriter;
+/// let pieces = vec!["a", "bc", "déf"];
+/// let lengths: Vec<usize> = pieces.iter().map(|piece| piece.len() + 1).collect();
+/// let writer = StringWriter::new(&lengths);
+/// pieces.par_iter().enumerate().for_each(|(i, piece)| {
+///     let mut region = writer.region(i);
+///     write!(region, "{piece};").unwrap();
+/// });
+/// assert_eq!(writer.finish().unwrap(), "a;bc;déf;");
+/// ```
+pub struct StringWriter {
+    bytes: Vec<u8>,
+    spare: UnsafeCellSlice<'static, MaybeUninit<u8>>,
+    offsets: Vec<usize>,
+    claimed: Vec<AtomicBool>,
+    filled: Vec<AtomicUsize>,
+    unvalidated: Vec<AtomicBool>,
+}
+
+impl core::fmt::Debug for StringWriter {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("StringWriter")
+            .field("offsets", &self.offsets)
+            .field("filled", &self.filled)
+            .finish_non_exhaustive()
+    }
+}
+
+impl StringWriter {
+    /// Create a new [`StringWriter`] with regions of byte lengths `region_lengths`.
+    ///
+    /// # Panics
+    /// Panics if the total length overflows a [`usize`].
+    #[must_use]
+    pub fn new(region_lengths: &[usize]) -> Self {
+        Self::with_string(String::new(), region_lengths)
+    }
+
+    /// Create a new [`StringWriter`] with regions of byte lengths `region_lengths` that are appended to `string`.
+    ///
+    /// # Panics
+    /// Panics if the total length overflows a [`usize`].
+    #[must_use]
+    pub fn with_string(string: String, region_lengths: &[usize]) -> Self {
+        let mut bytes = string.into_bytes();
+        let offsets = exclusive_prefix_sum(bytes.len(), region_lengths);
+        bytes.reserve_exact(offsets[region_lengths.len()] - bytes.len());
+        let spare = UnsafeCellSlice::from_spare_capacity(&mut bytes);
+        // SAFETY: the spare capacity is on the heap so it does not move with `bytes`, and `bytes` is not accessed until `finish`
+        let spare = unsafe {
+            UnsafeCellSlice::from_raw_parts(
+                NonNull::new_unchecked(spare.as_mut_ptr().cast::<MaybeUninit<u8>>()),
+                spare.len(),
+            )
+        };
+        Self {
+            bytes,
+            spare,
+            offsets,
+            claimed: region_lengths
+                .iter()
+                .map(|_| AtomicBool::new(false))
+                .collect(),
+            filled: region_lengths.iter().map(|_| AtomicUsize::new(0)).collect(),
+            unvalidated: region_lengths
+                .iter()
+                .map(|_| AtomicBool::new(false))
+                .collect(),
+        }
+    }
+
+    /// Return the number of regions.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.claimed.len()
+    }
+
+    /// Return whether there are no regions.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.claimed.is_empty()
+    }
+
+    /// Get a [`StrRegion`] writer for region `index`.
+    ///
+    /// Returns `None` if `index` is out of bounds or the region has already been requested.
+    #[must_use]
+    pub fn try_region(&self, index: usize) -> Option<StrRegion<'_>> {
+        if self.claimed.get(index)?.swap(true, Ordering::Relaxed) {
+            return None;
+        }
+        let start = self.offsets[index] - self.bytes.len();
+        let end = self.offsets[index + 1] - self.bytes.len();
+        Some(StrRegion {
+            index,
+            // SAFETY: each region is only requested once and regions do not overlap
+            buf: unsafe { self.spare.index_mut(start..end) },
+            len: 0,
+            filled: &self.filled[index],
+            unvalidated: &self.unvalidated[index],
+        })
+    }
+
+    /// Get a [`StrRegion`] writer for region `index`.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds or the region has already been requested.
+    #[must_use]
+    pub fn region(&self, index: usize) -> StrRegion<'_> {
+        self.try_region(index)
+            .expect("region index is out of bounds or has already been requested")
+    }
+
+    /// Finish writing, returning the [`String`].
+    ///
+    /// Regions written with [`StrRegion::push_bytes`] are validated as UTF-8.
+    ///
+    /// # Errors
+    /// Returns a [`StringWriterError`] if any region is not completely filled or is not valid UTF-8.
+    pub fn finish(self) -> Result<String, StringWriterError> {
+        self.finish_impl(false)
+    }
+
+    /// Finish writing, returning the [`String`] after validating all of it as UTF-8.
+    ///
+    /// # Errors
+    /// Returns a [`StringWriterError`] if any region is not completely filled or the string is not valid UTF-8.
+    pub fn finish_checked(self) -> Result<String, StringWriterError> {
+        self.finish_impl(true)
+    }
+
+    fn finish_impl(self, checked: bool) -> Result<String, StringWriterError> {
+        let Self {
+            mut bytes,
+            offsets,
+            filled,
+            unvalidated,
+            ..
+        } = self;
+        for (region, filled) in filled.iter().enumerate() {
+            let filled = filled.load(Ordering::Relaxed);
+            let len = offsets[region + 1] - offsets[region];
+            if filled != len {
+                return Err(StringWriterError::RegionNotFilled {
+                    region,
+                    filled,
+                    len,
+                });
+            }
+        }
+        // SAFETY: every region has been completely filled
+        unsafe { bytes.set_len(offsets[offsets.len() - 1]) };
+        if checked {
+            core::str::from_utf8(&bytes).map_err(StringWriterError::Utf8)?;
+        } else {
+            for (region, unvalidated) in unvalidated.iter().enumerate() {
+                if unvalidated.load(Ordering::Relaxed)
+                    && core::str::from_utf8(&bytes[offsets[region]..offsets[region + 1]]).is_err()
+                {
+                    // The preceding bytes are valid, so validating up to the end of the region reports the error relative to the whole string
+                    core::str::from_utf8(&bytes[..offsets[region + 1]])
+                        .map_err(StringWriterError::Utf8)?;
+                }
+            }
+        }
+        // SAFETY: the initial string and each region are valid UTF-8
+        Ok(unsafe { String::from_utf8_unchecked(bytes) })
+    }
+}
+
+/// A writer of one region of a [`StringWriter`].
+///
+/// This implements [`core::fmt::Write`], so it can be used with [`write!`].
+#[derive(Debug)]
+pub struct StrRegion<'a> {
+    index: usize,
+    buf: &'a mut [MaybeUninit<u8>],
+    len: usize,
+    filled: &'a AtomicUsize,
+    unvalidated: &'a AtomicBool,
+}
+
+impl StrRegion<'_> {
+    /// Return the number of bytes written to the region.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return whether no bytes have been written to the region.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Return the byte length of the region.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Append a string fragment to the region.
+    ///
+    /// # Errors
+    /// Returns [`StringWriterError::RegionOverflow`] if the fragment does not fit in the region.
+    pub fn push_str(&mut self, string: &str) -> Result<(), StringWriterError> {
+        self.push(string.as_bytes())
+    }
+
+    /// Append bytes to the region.
+    ///
+    /// The region is validated as UTF-8 by [`StringWriter::finish`].
+    ///
+    /// # Errors
+    /// Returns [`StringWriterError::RegionOverflow`] if the bytes do not fit in the region.
+    pub fn push_bytes(&mut self, bytes: &[u8]) -> Result<(), StringWriterError> {
+        self.unvalidated.store(true, Ordering::Relaxed);
+        self.push(bytes)
+    }
+
+    fn push(&mut self, bytes: &[u8]) -> Result<(), StringWriterError> {
+        let len = self.buf.len();
+        let dst = self.buf.get_mut(self.len..self.len + bytes.len()).ok_or(
+            StringWriterError::RegionOverflow {
+                region: self.index,
+                len,
+            },
+        )?;
+        for (dst, byte) in dst.iter_mut().zip(bytes) {
+            dst.write(*byte);
+        }
+        self.len += bytes.len();
+        self.filled.store(self.len, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+impl core::fmt::Write for StrRegion<'_> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.push_str(s).map_err(|_| core::fmt::Error)
+    }
+}
+
+/// A [`StringWriter`] error.
+///
+/// The offsets of a [`Utf8`](StringWriterError::Utf8) error are relative to the start of the whole [`String`], including its initial contents.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StringWriterError {
+    /// A write exceeded the length of a region.
+    RegionOverflow {
+        /// The region index.
+        region: usize,
+        /// The byte length of the region.
+        len: usize,
+    },
+    /// A region was not completely filled.
+    RegionNotFilled {
+        /// The region index.
+        region: usize,
+        /// The number of bytes written to the region.
+        filled: usize,
+        /// The byte length of the region.
+        len: usize,
+    },
+    /// The string is not valid UTF-8.
+    Utf8(core::str::Utf8Error),
+}
+
+impl core::fmt::Display for StringWriterError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::RegionOverflow { region, len } => {
+                write!(f, "write exceeds the length {len} of region {region}")
+            }
+            Self::RegionNotFilled {
+                region,
+                filled,
+                len,
+            } => write!(f, "region {region} is filled to {filled} of {len} bytes"),
+            Self::Utf8(err) => err.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for StringWriterError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Utf8(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fmt::Write, string::ToString};
+
+    use super::*;
+
+    #[test]
+    fn string_writer() {
+        let writer = StringWriter::with_string("<".to_string(), &[3, 0, 4]);
+        assert_eq!(writer.len(), 3);
+        let mut region_c = writer.region(2);
+        let mut region_a = writer.region(0);
+        assert!(writer.try_region(0).is_none());
+        write!(region_c, "{}", 1.5).unwrap();
+        region_c.push_str(">").unwrap();
+        region_a.push_str("αb").unwrap();
+        assert_eq!(
+            region_a.push_str("c"),
+            Err(StringWriterError::RegionOverflow { region: 0, len: 3 })
+        );
+        assert_eq!(region_a.len(), 3);
+        assert_eq!(writer.finish().unwrap(), "<αb1.5>");
+    }
+
+    #[test]
+    fn string_writer_not_filled() {
+        let writer = StringWriter::new(&[1, 2]);
+        writer.region(0).push_str("a").unwrap();
+        writer.region(1).push_str("b").unwrap();
+        assert_eq!(
+            writer.finish(),
+            Err(StringWriterError::RegionNotFilled {
+                region: 1,
+                filled: 1,
+                len: 2
+            })
+        );
+    }
+
+    #[test]
+    fn string_writer_bytes() {
+        let writer = StringWriter::new(&[2, 2]);
+        writer.region(0).push_bytes("é".as_bytes()).unwrap();
+        writer.region(1).push_str("xy").unwrap();
+        assert_eq!(writer.finish().unwrap(), "éxy");
+
+        for checked in [false, true] {
+            let writer = StringWriter::with_string("xy".to_string(), &[1, 2, 1]);
+            writer.region(0).push_str("z").unwrap();
+            writer.region(1).push_bytes(&[b'a', 0xff]).unwrap();
+            writer.region(2).push_str("b").unwrap();
+            let result = if checked {
+                writer.finish_checked()
+            } else {
+                writer.finish()
+            };
+            match result {
+                Err(StringWriterError::Utf8(err)) => assert_eq!(err.valid_up_to(), 4),
+                _ => panic!("region 1 is not valid UTF-8"),
+            }
+        }
+
+        let writer = StringWriter::new(&[1, 1]);
+        writer.region(0).push_bytes(&[0xc3]).unwrap();
+        writer.region(1).push_bytes(&[0xa9]).unwrap();
+        assert!(writer.finish().is_err());
+
+        let writer = StringWriter::new(&[1, 1]);
+        writer.region(0).push_bytes(&[0xc3]).unwrap();
+        writer.region(1).push_bytes(&[0xa9]).unwrap();
+        assert_eq!(writer.finish_checked().unwrap(), "é");
+    }
+}
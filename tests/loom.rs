@@ -0,0 +1,108 @@
+//! Loom model-checking tests.
+//!
+//! Run with `RUSTFLAGS="--cfg loom" cargo test --test loom --release`.
+
+#![cfg(loom)]
+
+use loom::thread;
+use unsafe_cell_slice::{Endian, UnsafeCellSlice};
+
+/// Leak a zeroed slice so that it can be shared with `'static` loom threads.
+fn leak<T: Clone + Default>(len: usize) -> &'static mut [T] {
+    Box::leak(vec![T::default(); len].into_boxed_slice())
+}
+
+#[test]
+fn loom_index_mut_disjoint_elements() {
+    loom::model(|| {
+        let data = UnsafeCellSlice::new(leak::<i64>(2));
+        let a = thread::spawn(move || *unsafe { data.index_mut(0) } += 1);
+        let b = thread::spawn(move || *unsafe { data.index_mut(1) } += 1);
+        a.join().unwrap();
+        b.join().unwrap();
+        assert_eq!(unsafe { data.index_mut(..) }, [1, 1]);
+    });
+}
+
+#[test]
+fn loom_index_mut_disjoint_subslices() {
+    loom::model(|| {
+        let data = UnsafeCellSlice::new(leak::<i64>(4));
+        let a = thread::spawn(move || unsafe { data.index_mut(..2) }.fill(1));
+        let b = thread::spawn(move || unsafe { data.index_mut(2..=3) }.fill(2));
+        a.join().unwrap();
+        b.join().unwrap();
+        assert_eq!(unsafe { data.index_mut(..) }, [1, 1, 2, 2]);
+    });
+}
+
+#[test]
+fn loom_index_mut_after_join() {
+    loom::model(|| {
+        let data = UnsafeCellSlice::new(leak::<i64>(1));
+        thread::spawn(move || *unsafe { data.index_mut(0) } = 1)
+            .join()
+            .unwrap();
+        assert_eq!(*unsafe { data.index_mut(0) }, 1);
+    });
+}
+
+#[test]
+#[should_panic(expected = "Causality violation")]
+fn loom_index_mut_overlapping() {
+    loom::model(|| {
+        let data = UnsafeCellSlice::new(leak::<i64>(3));
+        let a = thread::spawn(move || unsafe { data.index_mut(0..2) }.fill(1));
+        let b = thread::spawn(move || *unsafe { data.index_mut(1) } = 2);
+        a.join().unwrap();
+        b.join().unwrap();
+    });
+}
+
+#[test]
+fn loom_reused_memory() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    loom::model(|| {
+        let memory = leak::<i64>(2);
+        let ptr = memory.as_mut_ptr() as usize;
+        // Hand the memory over with synchronisation that loom does not observe, like an allocator reusing freed memory
+        let released: &'static AtomicBool = Box::leak(Box::new(AtomicBool::new(false)));
+        let a = thread::spawn(move || {
+            let data = UnsafeCellSlice::new(memory);
+            unsafe { data.index_mut(..) }.fill(1);
+            released.store(true, Ordering::Release);
+        });
+        while !released.load(Ordering::Acquire) {
+            thread::yield_now();
+        }
+        let memory = unsafe { std::slice::from_raw_parts_mut(ptr as *mut i64, 2) };
+        let data = UnsafeCellSlice::new(memory);
+        unsafe { data.index_mut(..) }.fill(2);
+        a.join().unwrap();
+    });
+}
+
+#[test]
+fn loom_channels_disjoint() {
+    loom::model(|| {
+        let data = UnsafeCellSlice::new(leak::<i64>(4));
+        let [mut even, odd] = unsafe { data.channels::<2>() };
+        let a = thread::spawn(move || even.iter_mut().for_each(|v| *v = 1));
+        let b = thread::spawn(move || odd.iter().sum::<i64>());
+        a.join().unwrap();
+        b.join().unwrap();
+    });
+}
+
+#[test]
+#[should_panic(expected = "Causality violation")]
+fn loom_read_at_overlapping() {
+    loom::model(|| {
+        let data = UnsafeCellSlice::new(leak::<u8>(3));
+        let a = thread::spawn(move || unsafe { data.write_at(0, 1u16, Endian::Little) });
+        let b = thread::spawn(move || unsafe { data.read_at::<u16>(1, Endian::Little) });
+        a.join().unwrap();
+        b.join().unwrap();
+    });
+}
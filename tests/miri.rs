@@ -0,0 +1,190 @@
+//! Aliasing tests of every [`SliceIndex`](unsafe_cell_slice::SliceIndex) impl.
+//!
+//! Run under Stacked Borrows with `cargo +nightly miri test --test miri` and under Tree Borrows with `MIRIFLAGS="-Zmiri-tree-borrows" cargo +nightly miri test --test miri`.
+
+use unsafe_cell_slice::UnsafeCellSlice;
+
+type Subslice = for<'a, 'b> fn(&'a UnsafeCellSlice<'b, i64>) -> &'a mut [i64];
+
+/// Write through interleaved mutable references to the disjoint `a` and `b`, then check the result through the original slice.
+fn ser_interleaved(a: Subslice, b: Subslice, expected: [i64; 4]) {
+    let mut data = vec![0i64; 4];
+    {
+        let data = UnsafeCellSlice::new(&mut data);
+        let data_a = a(&data);
+        let data_b = b(&data);
+        for _ in 0..2 {
+            data_a.iter_mut().for_each(|v| *v -= 1);
+            data_b.iter_mut().for_each(|v| *v += 1);
+        }
+    }
+    assert_eq!(data, expected);
+}
+
+/// Write through mutable references to the disjoint `a` and `b` on separate threads, then check the result through the original slice.
+fn par_disjoint(a: Subslice, b: Subslice, expected: [i64; 4]) {
+    let mut data = vec![0i64; 4];
+    {
+        let data = UnsafeCellSlice::new(&mut data);
+        std::thread::scope(|s| {
+            s.spawn(|| a(&data).iter_mut().for_each(|v| *v -= 2));
+            s.spawn(|| b(&data).iter_mut().for_each(|v| *v += 2));
+        });
+    }
+    assert_eq!(data, expected);
+}
+
+#[test]
+fn miri_ser_usize() {
+    ser_interleaved(
+        |data| core::slice::from_mut(unsafe { data.index_mut(0) }),
+        |data| core::slice::from_mut(unsafe { data.index_mut(3) }),
+        [-2, 0, 0, 2],
+    );
+}
+
+#[test]
+fn miri_par_usize() {
+    par_disjoint(
+        |data| core::slice::from_mut(unsafe { data.index_mut(0) }),
+        |data| core::slice::from_mut(unsafe { data.index_mut(3) }),
+        [-2, 0, 0, 2],
+    );
+}
+
+#[test]
+fn miri_ser_range() {
+    ser_interleaved(
+        |data| unsafe { data.index_mut(0..1) },
+        |data| unsafe { data.index_mut(1..4) },
+        [-2, 2, 2, 2],
+    );
+}
+
+#[test]
+fn miri_par_range() {
+    par_disjoint(
+        |data| unsafe { data.index_mut(0..1) },
+        |data| unsafe { data.index_mut(1..4) },
+        [-2, 2, 2, 2],
+    );
+}
+
+#[test]
+fn miri_ser_range_inclusive() {
+    ser_interleaved(
+        |data| unsafe { data.index_mut(0..=1) },
+        |data| unsafe { data.index_mut(2..=3) },
+        [-2, -2, 2, 2],
+    );
+}
+
+#[test]
+fn miri_par_range_inclusive() {
+    par_disjoint(
+        |data| unsafe { data.index_mut(0..=1) },
+        |data| unsafe { data.index_mut(2..=3) },
+        [-2, -2, 2, 2],
+    );
+}
+
+#[test]
+fn miri_ser_range_to_from() {
+    ser_interleaved(
+        |data| unsafe { data.index_mut(..3) },
+        |data| unsafe { data.index_mut(3..) },
+        [-2, -2, -2, 2],
+    );
+}
+
+#[test]
+fn miri_par_range_to_from() {
+    par_disjoint(
+        |data| unsafe { data.index_mut(..3) },
+        |data| unsafe { data.index_mut(3..) },
+        [-2, -2, -2, 2],
+    );
+}
+
+#[test]
+fn miri_ser_range_to_inclusive() {
+    ser_interleaved(
+        |data| unsafe { data.index_mut(..=0) },
+        |data| unsafe { data.index_mut(1..) },
+        [-2, 2, 2, 2],
+    );
+}
+
+#[test]
+fn miri_par_range_to_inclusive() {
+    par_disjoint(
+        |data| unsafe { data.index_mut(..=0) },
+        |data| unsafe { data.index_mut(1..) },
+        [-2, 2, 2, 2],
+    );
+}
+
+#[test]
+fn miri_ser_range_full() {
+    ser_interleaved(
+        |data| unsafe { data.index_mut(..) },
+        |data| unsafe { data.index_mut(4..) },
+        [-2, -2, -2, -2],
+    );
+}
+
+#[test]
+fn miri_par_range_full() {
+    par_disjoint(
+        |data| unsafe { data.index_mut(..) },
+        |data| unsafe { data.index_mut(4..) },
+        [-2, -2, -2, -2],
+    );
+}
+
+#[test]
+fn miri_ser_copy() {
+    let mut data = vec![0i64; 2];
+    {
+        let data = UnsafeCellSlice::new(&mut data);
+        let copy = data;
+        let data_a = unsafe { data.index_mut(0) };
+        let data_b = unsafe { copy.index_mut(1) };
+        *data_a -= 1;
+        *data_b += 1;
+        *data_a -= 1;
+    }
+    assert_eq!(data, [-2, 1]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn miri_ser_spare_capacity() {
+    let mut data: Vec<i64> = Vec::with_capacity(4);
+    data.push(-1);
+    {
+        let spare = UnsafeCellSlice::new_from_vec_with_spare_capacity(&mut data);
+        let spare_a = unsafe { spare.index_mut(0) };
+        let spare_b = unsafe { spare.index_mut(1..3) };
+        *spare_a = 1;
+        spare_b.fill(2);
+        *spare_a += 1;
+    }
+    unsafe { data.set_len(4) };
+    assert_eq!(data, [-1, 2, 2, 2]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn miri_par_spare_capacity() {
+    let mut data: Vec<i64> = Vec::with_capacity(4);
+    {
+        let spare = UnsafeCellSlice::new_from_vec_with_spare_capacity(&mut data);
+        std::thread::scope(|s| {
+            s.spawn(|| *unsafe { spare.index_mut(0) } = 1);
+            s.spawn(|| unsafe { spare.index_mut(1..4) }.fill(2));
+        });
+    }
+    unsafe { data.set_len(4) };
+    assert_eq!(data, [1, 2, 2, 2]);
+}
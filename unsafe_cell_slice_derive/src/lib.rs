@@ -0,0 +1,282 @@
+//! # unsafe_cell_slice_derive
+//!
+//! Derive macros for [`unsafe_cell_slice`](https://docs.rs/unsafe_cell_slice).
+//!
+//! These are re-exported by `unsafe_cell_slice` with the `derive` feature.
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Fields, GenericParam, Lifetime};
+
+/// Derive a struct-of-arrays [`UnsafeCellSlice`](https://docs.rs/unsafe_cell_slice/latest/unsafe_cell_slice/struct.UnsafeCellSlice.html) view for a struct with named fields implementing `SoAField` (e.g. `Vec<T>`, `&mut [T]`, `Box<[T]>`).
+///
+/// For a struct `Name`, this generates:
+///  - `NameCellView<'a>`: a view holding an `UnsafeCellSlice` per field, created by `Name::cell_view()` or `NameCellView::new()`,
+///  - `NameRefsMut<'a>`: mutable references to the elements of each field at an index, returned by `NameCellView::{get_mut,index_mut}()`, and
+///  - `NameSlicesMut<'a>`: mutable subslices of each field, returned by `NameCellView::{get_range_mut,index_range_mut,get_chunk_mut,index_chunk_mut}()`.
+///
+/// The fields of the generated structs are public and have the same names as the fields of the struct.
+/// All fields must have the same length, which is checked when the view is created.
+#[proc_macro_derive(UnsafeCellSoA)]
+pub fn derive_unsafe_cell_soa(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match soa(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn soa(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) if !fields.named.is_empty() => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    input,
+                    "UnsafeCellSoA requires a struct with at least one named field",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "UnsafeCellSoA can only be derived for structs",
+            ))
+        }
+    };
+
+    let crate_path = quote!(::unsafe_cell_slice);
+    let vis = &input.vis;
+    let name = &input.ident;
+    let view = format_ident!("{}CellView", name);
+    let refs = format_ident!("{}RefsMut", name);
+    let slices = format_ident!("{}SlicesMut", name);
+
+    let lifetime = Lifetime::new("'__unsafe_cell_soa", Span::call_site());
+    let mut generics = input.generics.clone();
+    generics
+        .params
+        .insert(0, GenericParam::Lifetime(parse_quote!(#lifetime)));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let (src_impl_generics, src_ty_generics, src_where_clause) = input.generics.split_for_impl();
+    let params = &generics.params;
+    let args: Vec<_> = input
+        .generics
+        .params
+        .iter()
+        .map(|param| match param {
+            GenericParam::Lifetime(param) => {
+                let lifetime = &param.lifetime;
+                quote!(#lifetime)
+            }
+            GenericParam::Type(param) => {
+                let ident = &param.ident;
+                quote!(#ident)
+            }
+            GenericParam::Const(param) => {
+                let ident = &param.ident;
+                quote!(#ident)
+            }
+        })
+        .collect();
+    let source = quote!(#name #src_ty_generics);
+
+    let idents: Vec<_> = fields
+        .iter()
+        .map(|field| field.ident.as_ref().expect("named field"))
+        .collect();
+    let names: Vec<_> = idents.iter().map(|ident| ident.to_string()).collect();
+    let elems: Vec<_> = fields
+        .iter()
+        .map(|field| {
+            let ty = &field.ty;
+            quote!(<#ty as #crate_path::SoAField>::Elem)
+        })
+        .collect();
+    let first = idents[0];
+
+    let view_doc = format!("An [`UnsafeCellSlice`](::unsafe_cell_slice::UnsafeCellSlice) view of each field of [`{name}`] with equal lengths.");
+    let refs_doc =
+        format!("Mutable references to the elements of each field of [`{name}`] at an index.");
+    let slices_doc = format!("Mutable subslices of each field of [`{name}`].");
+
+    Ok(quote! {
+        #[doc = #view_doc]
+        #vis struct #view<#params> #where_clause {
+            #(
+                #[allow(missing_docs)]
+                pub #idents: #crate_path::UnsafeCellSlice<#lifetime, #elems>,
+            )*
+            _marker: ::core::marker::PhantomData<&#lifetime mut #source>,
+        }
+
+        #[doc = #refs_doc]
+        #vis struct #refs<#params> #where_clause {
+            #(
+                #[allow(missing_docs)]
+                pub #idents: &#lifetime mut #elems,
+            )*
+            _marker: ::core::marker::PhantomData<&#lifetime mut #source>,
+        }
+
+        #[doc = #slices_doc]
+        #vis struct #slices<#params> #where_clause {
+            #(
+                #[allow(missing_docs)]
+                pub #idents: &#lifetime mut [#elems],
+            )*
+            _marker: ::core::marker::PhantomData<&#lifetime mut #source>,
+        }
+
+        impl #src_impl_generics #source #src_where_clause {
+            /// Create an [`UnsafeCellSlice`](::unsafe_cell_slice::UnsafeCellSlice) view of each field.
+            ///
+            /// # Errors
+            /// Returns a [`SoALengthError`](::unsafe_cell_slice::SoALengthError) if the fields do not have equal lengths.
+            #vis fn cell_view(&mut self) -> ::core::result::Result<#view<'_, #(#args),*>, #crate_path::SoALengthError> {
+                #view::new(self)
+            }
+        }
+
+        impl #impl_generics #view #ty_generics #where_clause {
+            /// Create an [`UnsafeCellSlice`](::unsafe_cell_slice::UnsafeCellSlice) view of each field.
+            ///
+            /// # Errors
+            /// Returns a [`SoALengthError`](::unsafe_cell_slice::SoALengthError) if the fields do not have equal lengths.
+            #vis fn new(soa: &#lifetime mut #source) -> ::core::result::Result<Self, #crate_path::SoALengthError> {
+                let view = Self {
+                    #(
+                        #idents: #crate_path::UnsafeCellSlice::new(
+                            #crate_path::SoAField::as_mut_slice(&mut soa.#idents)
+                        ),
+                    )*
+                    _marker: ::core::marker::PhantomData,
+                };
+                let len = view.#first.len();
+                #(
+                    if view.#idents.len() != len {
+                        return ::core::result::Result::Err(#crate_path::SoALengthError {
+                            field: #names,
+                            expected: len,
+                            found: view.#idents.len(),
+                        });
+                    }
+                )*
+                ::core::result::Result::Ok(view)
+            }
+
+            /// Return the length of each field.
+            #[must_use]
+            #vis fn len(&self) -> usize {
+                self.#first.len()
+            }
+
+            /// Return whether the fields are empty.
+            #[must_use]
+            #vis fn is_empty(&self) -> bool {
+                self.#first.is_empty()
+            }
+
+            /// Get mutable references to the elements of each field at `index`.
+            ///
+            /// Returns `None` if the index is out of bounds.
+            ///
+            /// # Safety
+            /// This is very unsafe because it is capable of creating multiple mutable references to the same data.
+            /// It is the responsibility of the caller to only access non-overlapping elements to avoid data races and undefined behavior.
+            #[must_use]
+            #vis unsafe fn get_mut(&self, index: usize) -> ::core::option::Option<#refs<'_, #(#args),*>> {
+                ::core::option::Option::Some(#refs {
+                    #(#idents: self.#idents.get_mut(index)?,)*
+                    _marker: ::core::marker::PhantomData,
+                })
+            }
+
+            /// Get mutable references to the elements of each field at `index`.
+            ///
+            /// # Safety
+            /// This is very unsafe because it is capable of creating multiple mutable references to the same data.
+            /// It is the responsibility of the caller to only access non-overlapping elements to avoid data races and undefined behavior.
+            ///
+            /// # Panics
+            /// Panics if the index is out of bounds.
+            #[must_use]
+            #vis unsafe fn index_mut(&self, index: usize) -> #refs<'_, #(#args),*> {
+                self.get_mut(index).expect("index out of bounds")
+            }
+
+            /// Get mutable subslices of each field in `range`.
+            ///
+            /// Returns `None` if the range is out of bounds.
+            ///
+            /// # Safety
+            /// This is very unsafe because it is capable of creating multiple mutable references to the same data.
+            /// It is the responsibility of the caller to only access non-overlapping subslices to avoid data races and undefined behavior.
+            #[must_use]
+            #vis unsafe fn get_range_mut(&self, range: ::core::ops::Range<usize>) -> ::core::option::Option<#slices<'_, #(#args),*>> {
+                ::core::option::Option::Some(#slices {
+                    #(#idents: self.#idents.get_mut(range.clone())?,)*
+                    _marker: ::core::marker::PhantomData,
+                })
+            }
+
+            /// Get mutable subslices of each field in `range`.
+            ///
+            /// # Safety
+            /// This is very unsafe because it is capable of creating multiple mutable references to the same data.
+            /// It is the responsibility of the caller to only access non-overlapping subslices to avoid data races and undefined behavior.
+            ///
+            /// # Panics
+            /// Panics if the range is out of bounds.
+            #[must_use]
+            #vis unsafe fn index_range_mut(&self, range: ::core::ops::Range<usize>) -> #slices<'_, #(#args),*> {
+                self.get_range_mut(range).expect("index out of bounds")
+            }
+
+            /// Return the number of chunks of `chunk_size` elements, including a final partial chunk.
+            ///
+            /// # Panics
+            /// Panics if `chunk_size` is zero.
+            #[must_use]
+            #vis fn num_chunks(&self, chunk_size: usize) -> usize {
+                assert!(chunk_size != 0, "chunk size must be non-zero");
+                self.len() / chunk_size + usize::from(self.len() % chunk_size != 0)
+            }
+
+            /// Get mutable subslices of each field in chunk `chunk_index` of `chunk_size` elements.
+            ///
+            /// The final chunk may be shorter than `chunk_size`.
+            /// Returns `None` if the chunk is out of bounds.
+            ///
+            /// # Safety
+            /// This is very unsafe because it is capable of creating multiple mutable references to the same data.
+            /// It is the responsibility of the caller to only access non-overlapping subslices to avoid data races and undefined behavior.
+            ///
+            /// # Panics
+            /// Panics if `chunk_size` is zero.
+            #[must_use]
+            #vis unsafe fn get_chunk_mut(&self, chunk_index: usize, chunk_size: usize) -> ::core::option::Option<#slices<'_, #(#args),*>> {
+                assert!(chunk_size != 0, "chunk size must be non-zero");
+                let start = chunk_index.checked_mul(chunk_size).filter(|start| *start < self.len())?;
+                let end = start.saturating_add(chunk_size).min(self.len());
+                self.get_range_mut(start..end)
+            }
+
+            /// Get mutable subslices of each field in chunk `chunk_index` of `chunk_size` elements.
+            ///
+            /// The final chunk may be shorter than `chunk_size`.
+            ///
+            /// # Safety
+            /// This is very unsafe because it is capable of creating multiple mutable references to the same data.
+            /// It is the responsibility of the caller to only access non-overlapping subslices to avoid data races and undefined behavior.
+            ///
+            /// # Panics
+            /// Panics if `chunk_size` is zero or the chunk is out of bounds.
+            #[must_use]
+            #vis unsafe fn index_chunk_mut(&self, chunk_index: usize, chunk_size: usize) -> #slices<'_, #(#args),*> {
+                self.get_chunk_mut(chunk_index, chunk_size).expect("index out of bounds")
+            }
+        }
+    })
+}
@@ -0,0 +1,106 @@
+use rayon::prelude::*;
+use unsafe_cell_slice::{SoALengthError, UnsafeCellSoA};
+
+#[derive(UnsafeCellSoA)]
+struct Particles {
+    position: Vec<[f32; 2]>,
+    velocity: Box<[[f32; 2]]>,
+    id: Vec<u32>,
+}
+
+#[derive(UnsafeCellSoA)]
+pub struct Generic<'a, T: Copy, const N: usize> {
+    a: &'a mut [T],
+    b: [u8; N],
+}
+
+fn particles(len: usize) -> Particles {
+    Particles {
+        position: vec![[0.0; 2]; len],
+        velocity: vec![[1.0, 2.0]; len].into_boxed_slice(),
+        id: vec![0; len],
+    }
+}
+
+#[test]
+fn soa_get_mut_ser() {
+    let mut particles = particles(3);
+    {
+        let view = particles.cell_view().unwrap();
+        assert_eq!(view.len(), 3);
+        for i in 0..view.len() {
+            let p = unsafe { view.index_mut(i) };
+            p.position[0] += p.velocity[0];
+            p.position[1] += p.velocity[1];
+            *p.id = i as u32;
+        }
+        assert!(unsafe { view.get_mut(3) }.is_none());
+    }
+    assert_eq!(particles.position, [[1.0, 2.0]; 3]);
+    assert_eq!(particles.id, [0, 1, 2]);
+}
+
+#[test]
+fn soa_get_mut_par() {
+    let mut particles = particles(100);
+    {
+        let view = ParticlesCellView::new(&mut particles).unwrap();
+        (0..view.len()).into_par_iter().for_each(|i| {
+            let p = unsafe { view.index_mut(i) };
+            *p.id = i as u32;
+        });
+    }
+    assert!(particles
+        .id
+        .iter()
+        .enumerate()
+        .all(|(i, id)| *id == i as u32));
+}
+
+#[test]
+fn soa_range_chunk() {
+    let mut particles = particles(5);
+    {
+        let view = particles.cell_view().unwrap();
+        let ParticlesSlicesMut { id, .. } = unsafe { view.index_range_mut(1..3) };
+        id.fill(1);
+        assert!(unsafe { view.get_range_mut(4..6) }.is_none());
+        assert_eq!(view.num_chunks(2), 3);
+        let chunk = unsafe { view.index_chunk_mut(2, 2) };
+        assert_eq!(chunk.id.len(), 1);
+        chunk.id[0] = 2;
+        assert!(unsafe { view.get_chunk_mut(3, 2) }.is_none());
+    }
+    assert_eq!(particles.id, [0, 1, 1, 0, 2]);
+}
+
+#[test]
+fn soa_length_mismatch() {
+    let mut particles = particles(2);
+    particles.id.push(0);
+    assert_eq!(
+        particles.cell_view().err(),
+        Some(SoALengthError {
+            field: "id",
+            expected: 2,
+            found: 3
+        })
+    );
+}
+
+#[test]
+fn soa_generic() {
+    let mut a = [0u16; 2];
+    let mut generic = Generic::<u16, 2> {
+        a: &mut a,
+        b: [0; 2],
+    };
+    {
+        let view = generic.cell_view().unwrap();
+        let refs = unsafe { view.index_mut(1) };
+        *refs.a = 1;
+        *refs.b = 2;
+    }
+    assert_eq!(generic.b, [0, 2]);
+    assert_eq!(a, [0, 1]);
+}